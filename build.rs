@@ -0,0 +1,27 @@
+//! Embeds a build of oranda's CSS into the binary, for use in `--offline` builds.
+//!
+//! The CSS has to be built with tailwind first (`cd oranda-css && yarn install && yarn run build`).
+//! Release builds refuse to build without it, so every oranda we ship can build offline. Debug
+//! builds warn and embed nothing, so `--offline` builds from them need the CSS to be cached.
+
+use std::path::{Path, PathBuf};
+use std::{env, fs};
+
+const BUILT_CSS: &str = "oranda-css/dist/oranda.css";
+
+fn main() {
+    println!("cargo:rerun-if-changed={BUILT_CSS}");
+
+    let out_dir = PathBuf::from(env::var("OUT_DIR").expect("OUT_DIR wasn't set by cargo"));
+    let css = match fs::read_to_string(Path::new(BUILT_CSS)) {
+        Ok(css) => css,
+        Err(e) if env::var("PROFILE").as_deref() == Ok("release") => {
+            panic!("couldn't read {BUILT_CSS} ({e}), which release builds embed for --offline builds. Build it with `cd oranda-css && yarn install && yarn run build` first.");
+        }
+        Err(_) => {
+            println!("cargo:warning={BUILT_CSS} hasn't been built, so it won't be embedded for --offline builds");
+            String::new()
+        }
+    };
+    fs::write(out_dir.join("oranda.css"), css).expect("failed to write embedded oranda.css");
+}
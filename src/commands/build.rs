@@ -3,6 +3,7 @@ use clap::Parser;
 
 use crate::message::{Message, MessageType};
 use oranda::config::Config;
+use oranda::data::cache::CacheMode;
 use oranda::errors::*;
 use oranda::site::Site;
 
@@ -22,20 +23,43 @@ pub struct Build {
     #[clap(hide = true)]
     #[arg(long, default_value = "./oranda.json")]
     config_path: Utf8PathBuf,
+    /// Ignore any cached releases and files, and fetch everything again
+    #[arg(long)]
+    refresh: bool,
+    /// Don't use the network at all, only cached releases and files
+    #[arg(long, conflicts_with = "refresh")]
+    offline: bool,
+    /// How the cache should be used, if the caller knows better than our flags
+    #[clap(skip)]
+    cache_mode: Option<CacheMode>,
 }
 
 impl Build {
-    pub fn new(project_root: Option<Utf8PathBuf>, config_path: Option<Utf8PathBuf>) -> Self {
+    pub fn new(
+        project_root: Option<Utf8PathBuf>,
+        config_path: Option<Utf8PathBuf>,
+        cache_mode: CacheMode,
+    ) -> Self {
         Build {
             project_root: project_root.unwrap_or(Utf8PathBuf::from("./")),
             config_path: config_path.unwrap_or(Utf8PathBuf::from("./oranda.json")),
+            refresh: false,
+            offline: false,
+            cache_mode: Some(cache_mode),
         }
     }
 
     pub fn run(&self) -> Result<()> {
         Message::new(MessageType::Info, "Running build...").print();
         tracing::info!("Running build...");
-        let config = Config::build(&self.config_path)?;
+        let mut config = Config::build(&self.config_path)?;
+        config.build.cache_mode = self.cache_mode.unwrap_or(if self.offline {
+            CacheMode::Offline
+        } else if self.refresh {
+            CacheMode::Refresh
+        } else {
+            CacheMode::Revalidate
+        });
         Site::build(&config)?.write(&config)?;
         let msg = format!(
             "Successfully built your site in the `{}` directory. To view, run `oranda serve`.",
@@ -13,6 +13,7 @@ use crate::{
 };
 use oranda::{
     config::Config,
+    data::cache::CacheMode,
     errors::*,
     site::mdbook::{custom_theme, load_mdbook, mdbook_dir},
 };
@@ -42,6 +43,12 @@ pub struct Dev {
     /// List of extra paths to watch
     #[arg(short, long)]
     include_paths: Option<Vec<Utf8PathBuf>>,
+    /// Ignore any cached releases and files, and fetch everything again on the first build
+    #[arg(long)]
+    refresh: bool,
+    /// Don't use the network at all, only cached releases and files
+    #[arg(long, conflicts_with = "refresh")]
+    offline: bool,
 }
 
 impl Dev {
@@ -60,15 +67,19 @@ impl Dev {
                 .unwrap_or(Utf8PathBuf::from("./oranda.json")),
         )?;
         let mut paths_to_watch = vec![];
+        // Paths that can change what we fetch over the network (releases, oranda's CSS...).
+        // If none of these change, rebuilds reuse whatever's in the cache.
+        let mut network_paths = vec![];
         // Watch for the readme file
         paths_to_watch.push(config.project.readme_path);
         // Watch for the oranda config file
-        paths_to_watch.push(
-            self.config_path
-                .clone()
-                .unwrap_or(Utf8PathBuf::from("./oranda.json"))
-                .into(),
-        );
+        let config_path: String = self
+            .config_path
+            .clone()
+            .unwrap_or(Utf8PathBuf::from("./oranda.json"))
+            .into();
+        network_paths.push(config_path.clone());
+        paths_to_watch.push(config_path);
 
         // Watch for any user-provided paths
         if let Some(include_paths) = &self.include_paths {
@@ -112,11 +123,17 @@ impl Dev {
         // Watch for any project manifest files
         let project = axoproject::get_workspaces("./".into(), None);
         if let WorkspaceSearch::Found(workspace) = project.rust {
+            network_paths.push(workspace.manifest_path.to_string());
             paths_to_watch.push(workspace.manifest_path.into());
         }
         if let WorkspaceSearch::Found(workspace) = project.javascript {
+            network_paths.push(workspace.manifest_path.to_string());
             paths_to_watch.push(workspace.manifest_path.into());
         }
+        let network_paths: Vec<PathBuf> = network_paths
+            .into_iter()
+            .filter_map(|path| PathBuf::from(path).canonicalize().ok())
+            .collect();
 
         let (tx, rx) = std::sync::mpsc::channel();
 
@@ -157,7 +174,19 @@ impl Dev {
         .print();
 
         if !self.no_first_build {
-            Build::new(self.project_root.clone(), self.config_path.clone()).run()?;
+            let cache_mode = if self.offline {
+                CacheMode::Offline
+            } else if self.refresh {
+                CacheMode::Refresh
+            } else {
+                CacheMode::Revalidate
+            };
+            Build::new(
+                self.project_root.clone(),
+                self.config_path.clone(),
+                cache_mode,
+            )
+            .run()?;
         }
 
         // Spawn the serve process out into a separate thread so that we can loop through received events on this thread
@@ -196,8 +225,25 @@ impl Dev {
                 )
                 .print();
 
-                if let Err(e) =
-                    Build::new(self.project_root.clone(), self.config_path.clone()).run()
+                // Only go back to the network if something that affects what we fetch changed
+                let network_changed = paths.iter().any(|path| {
+                    path.canonicalize()
+                        .map(|path| network_paths.contains(&path))
+                        .unwrap_or(false)
+                });
+                let cache_mode = if self.offline {
+                    CacheMode::Offline
+                } else if network_changed {
+                    CacheMode::Revalidate
+                } else {
+                    CacheMode::Reuse
+                };
+                if let Err(e) = Build::new(
+                    self.project_root.clone(),
+                    self.config_path.clone(),
+                    cache_mode,
+                )
+                .run()
                 {
                     eprintln!("{:?}", Report::new(e));
                     continue;
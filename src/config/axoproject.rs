@@ -1,8 +1,11 @@
-use axoproject::{PackageIdx, WorkspaceInfo, WorkspaceSearch};
+use axoasset::LocalAsset;
+use axoproject::{PackageIdx, WorkspaceInfo, WorkspaceKind, WorkspaceSearch};
 use camino::{Utf8Path, Utf8PathBuf};
+use indexmap::IndexMap;
+use serde::Deserialize;
 use std::path::PathBuf;
 
-use super::ProjectLayer;
+use super::{AutoPackageManager, DetectedPackage, ProjectLayer};
 use crate::{
     errors::*,
     message::{Message, MessageType},
@@ -15,6 +18,20 @@ pub struct AxoprojectConfig {
     pub project: ProjectLayer,
     /// Did they have cargo_dist settings?
     pub cargo_dist: Option<bool>,
+    /// Packages that can be installed with package managers
+    pub packages: Vec<DetectedPackage>,
+}
+
+/// The parts of a `pyproject.toml` we care about
+#[derive(Debug, Deserialize)]
+struct PyProject {
+    project: Option<PyProjectTable>,
+}
+#[derive(Debug, Deserialize)]
+struct PyProjectTable {
+    name: String,
+    #[serde(default)]
+    scripts: IndexMap<String, String>,
 }
 
 impl AxoprojectConfig {
@@ -25,10 +42,42 @@ impl AxoprojectConfig {
         });
         let start_dir = Utf8PathBuf::from_path_buf(start_dir).expect("project path isn't utf8!?");
 
+        // axoproject doesn't know about Python, so we look for that ourselves
+        let python_project = AxoprojectConfig::get_python_project(&start_dir);
+        let python_packages = python_project
+            .as_ref()
+            .filter(|project| !project.scripts.is_empty())
+            .map(|project| DetectedPackage {
+                manager: AutoPackageManager::Pipx,
+                name: project.name.clone(),
+                binaries: project.scripts.keys().cloned().collect(),
+            });
+
         if let Some((workspace, pkg)) = AxoprojectConfig::get_project(&start_dir) {
             // Cool we found the best possible match, now extract all the values we care about from it
             let package = workspace.package(pkg);
 
+            // If the package has binaries, people can install it with its package manager
+            let mut packages = vec![];
+            if !package.binaries.is_empty() && package.publish {
+                let managers: &[AutoPackageManager] = match workspace.kind {
+                    WorkspaceKind::Rust => {
+                        &[AutoPackageManager::Cargo, AutoPackageManager::Binstall]
+                    }
+                    WorkspaceKind::Javascript => {
+                        &[AutoPackageManager::Npm, AutoPackageManager::Npx]
+                    }
+                };
+                for manager in managers {
+                    packages.push(DetectedPackage {
+                        manager: *manager,
+                        name: package.name.clone(),
+                        binaries: package.binaries.clone(),
+                    });
+                }
+            }
+            packages.extend(python_packages);
+
             // If there's a [workspace.metadata.dist] table, we can auto-enable cargo-dist
             // If there's no [workspace.metadata] table at all, inconclusive.
             let cargo_dist = workspace
@@ -46,12 +95,48 @@ impl AxoprojectConfig {
                     readme_path: package.readme_file.as_ref().map(|v| v.to_string()),
                 },
                 cargo_dist,
+                packages,
+            }))
+        } else if let Some(python_packages) = python_packages {
+            // We only use the pyproject.toml for install hints, not for project info
+            Ok(Some(AxoprojectConfig {
+                project: ProjectLayer {
+                    name: None,
+                    description: None,
+                    homepage: None,
+                    repository: None,
+                    version: None,
+                    license: None,
+                    readme_path: None,
+                },
+                cargo_dist: None,
+                packages: vec![python_packages],
             }))
         } else {
             Ok(None)
         }
     }
 
+    /// Get the `[project]` table of a `pyproject.toml` in the start_dir, if there is one
+    fn get_python_project(start_dir: &Utf8Path) -> Option<PyProjectTable> {
+        let manifest_path = start_dir.join("pyproject.toml");
+        if !manifest_path.exists() {
+            return None;
+        }
+        let parsed = LocalAsset::load_string(&manifest_path)
+            .map_err(|e| e.to_string())
+            .and_then(|contents| toml::from_str::<PyProject>(&contents).map_err(|e| e.to_string()));
+        match parsed {
+            Ok(pyproject) => pyproject.project,
+            Err(e) => {
+                let message =
+                    format!("Ignoring {manifest_path}, because we couldn't parse it: {e}");
+                Message::new(MessageType::Warning, &message).print();
+                None
+            }
+        }
+    }
+
     /// Get information about the project workspace (using axoproject)
     ///
     /// The returned value is info about a Workspace and the specific package in that
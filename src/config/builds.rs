@@ -3,6 +3,7 @@ use schemars::JsonSchema;
 use serde::Deserialize;
 
 use super::{ApplyLayer, ApplyOptExt, ApplyValExt};
+use crate::data::cache::CacheMode;
 
 #[derive(Debug)]
 /// Information about how the pages should be built (complete version)
@@ -21,6 +22,10 @@ pub struct BuildConfig {
     ///
     /// We use IndexMap to respect the order the user provided.
     pub additional_pages: IndexMap<String, String>,
+    /// How the on-disk cache of fetched files should be used
+    ///
+    /// This isn't set by oranda.json, but by CLI flags like `--refresh`.
+    pub cache_mode: CacheMode,
 }
 #[derive(Debug, Deserialize, JsonSchema)]
 /// Information about how the pages should be built (partial version used by oranda.json)
@@ -48,6 +53,7 @@ impl Default for BuildConfig {
             static_dir: "static".to_owned(),
             path_prefix: None,
             additional_pages: Default::default(),
+            cache_mode: CacheMode::default(),
         }
     }
 }
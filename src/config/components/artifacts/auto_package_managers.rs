@@ -0,0 +1,122 @@
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+use crate::config::{ApplyLayer, ApplyValExt};
+
+/// Package managers we can suggest on our own, based on the project's manifest
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum AutoPackageManager {
+    /// `cargo install`
+    Cargo,
+    /// `cargo binstall`
+    Binstall,
+    /// `npm install -g`
+    Npm,
+    /// `npx`
+    Npx,
+    /// `pipx install`
+    Pipx,
+}
+
+impl AutoPackageManager {
+    /// The label of the installer we suggest
+    pub fn label(self) -> &'static str {
+        match self {
+            AutoPackageManager::Cargo => "cargo",
+            AutoPackageManager::Binstall => "binstall",
+            AutoPackageManager::Npm => "npm",
+            AutoPackageManager::Npx => "npx",
+            AutoPackageManager::Pipx => "pipx",
+        }
+    }
+}
+
+/// A package in the project that can be installed with a package manager
+#[derive(Debug, Clone)]
+pub struct DetectedPackage {
+    /// The package manager that can install it
+    pub manager: AutoPackageManager,
+    /// The name of the package (e.g. "@axodotdev/oranda")
+    pub name: String,
+    /// The binaries the package provides
+    pub binaries: Vec<String>,
+}
+
+/// Which package managers to suggest on our own (complete version)
+#[derive(Debug)]
+pub struct AutoPackageManagersConfig {
+    pub cargo: bool,
+    pub binstall: bool,
+    pub npm: bool,
+    pub npx: bool,
+    pub pipx: bool,
+    /// The packages we found in the project's manifests
+    ///
+    /// This is set by the axoproject layer, not oranda.json.
+    pub detected: Vec<DetectedPackage>,
+}
+/// Which package managers to suggest on our own (partial version used by oranda.json)
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct AutoPackageManagersLayer {
+    /// Suggest `cargo install` for Rust packages with binaries
+    pub cargo: Option<bool>,
+    /// Suggest `cargo binstall` for Rust packages with binaries
+    pub binstall: Option<bool>,
+    /// Suggest `npm install -g` for npm packages with a `bin`
+    pub npm: Option<bool>,
+    /// Suggest `npx` for npm packages with a `bin`
+    pub npx: Option<bool>,
+    /// Suggest `pipx install` for Python packages with scripts
+    pub pipx: Option<bool>,
+}
+
+impl Default for AutoPackageManagersConfig {
+    fn default() -> Self {
+        AutoPackageManagersConfig {
+            cargo: true,
+            binstall: true,
+            npm: true,
+            npx: true,
+            pipx: true,
+            detected: vec![],
+        }
+    }
+}
+impl ApplyLayer for AutoPackageManagersConfig {
+    type Layer = AutoPackageManagersLayer;
+    fn apply_layer(&mut self, layer: Self::Layer) {
+        // This is intentionally written slightly cumbersome to make you update this
+        let AutoPackageManagersLayer {
+            cargo,
+            binstall,
+            npm,
+            npx,
+            pipx,
+        } = layer;
+        self.cargo.apply_val(cargo);
+        self.binstall.apply_val(binstall);
+        self.npm.apply_val(npm);
+        self.npx.apply_val(npx);
+        self.pipx.apply_val(pipx);
+    }
+}
+
+impl AutoPackageManagersConfig {
+    /// Whether we're allowed to suggest this package manager
+    pub fn is_enabled(&self, manager: AutoPackageManager) -> bool {
+        match manager {
+            AutoPackageManager::Cargo => self.cargo,
+            AutoPackageManager::Binstall => self.binstall,
+            AutoPackageManager::Npm => self.npm,
+            AutoPackageManager::Npx => self.npx,
+            AutoPackageManager::Pipx => self.pipx,
+        }
+    }
+
+    /// Get the packages we're allowed to suggest
+    pub fn enabled(&self) -> impl Iterator<Item = &DetectedPackage> {
+        self.detected
+            .iter()
+            .filter(|package| self.is_enabled(package.manager))
+    }
+}
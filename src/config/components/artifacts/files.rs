@@ -0,0 +1,77 @@
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+/// Overrides for what we know about files matching a glob
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema)]
+pub struct ArtifactFileConfig {
+    /// The platforms the file is for, either as systems (e.g. "mac" or "windows-arm")
+    /// or as target triples (e.g. "x86_64-unknown-linux-gnu")
+    pub targets: Option<Vec<ArtifactTarget>>,
+    /// A brief label for the installer (e.g. "dmg")
+    pub label: Option<String>,
+    /// A longer description of the installer
+    pub description: Option<String>,
+    /// How much the file should be preferred over other ways to install
+    pub preference: Option<ArtifactPreference>,
+}
+
+/// A platform that a file is for
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum ArtifactTarget {
+    /// A system like "mac" or "linux"
+    System(ArtifactSystem),
+    /// A target triple like "x86_64-unknown-linux-gnu"
+    Triple(String),
+}
+
+/// A system that artifacts can be for
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum ArtifactSystem {
+    Windows,
+    Windows64,
+    WindowsArm,
+
+    Mac,
+    MacPpc,
+    Mac32,
+    Mac64,
+    MacSilicon,
+
+    Linux,
+    Linux64,
+    LinuxArm,
+    LinuxUbuntu,
+    LinuxDebian,
+    LinuxMandriva,
+    LinuxRedhat,
+    LinuxFedora,
+    LinuxSuse,
+    LinuxGentoo,
+
+    Ios,
+    Android,
+
+    Freebsd,
+    Netbsd,
+    Illumos,
+
+    Wasi,
+}
+
+/// How much a file should be preferred over other ways to install (descending order)
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ArtifactPreference {
+    /// The best way
+    Preferred,
+    /// Some kind of "native" installer like a .msi or .dmg
+    Native,
+    /// Some kind of curl|sh script
+    Script,
+    /// Any kind of custom/misc/unknown solution
+    Custom,
+    /// Just a tarball containing the binary
+    Archive,
+}
@@ -0,0 +1,19 @@
+use indexmap::IndexMap;
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+use super::ArtifactTarget;
+
+/// Overrides for how an installer is shown, keyed by the installer's label
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema)]
+pub struct InstallerConfig {
+    /// The label to show for the installer instead
+    pub label: Option<String>,
+    /// A one-line explanation of the installer, shown under its tab
+    pub description: Option<String>,
+    /// Where to put this installer on specific platforms (lower numbers come first)
+    ///
+    /// Keys are systems (e.g. "mac") or target triples. Installers without a priority for
+    /// a platform come after those with one.
+    pub priority: Option<IndexMap<ArtifactTarget, i64>>,
+}
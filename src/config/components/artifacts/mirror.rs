@@ -0,0 +1,15 @@
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+/// A mirror that release files are also hosted on (e.g. a CDN)
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct MirrorConfig {
+    /// A template for the url of a file on the mirror
+    ///
+    /// `{tag}`, `{name}`, `{owner}` and `{repo}` are replaced with the release's tag, the file's
+    /// name, and the owner and name of the project's repository.
+    pub url: String,
+    /// Whether to also link to where files were originally published in the downloads table
+    #[serde(default)]
+    pub show_origin: bool,
+}
@@ -1,37 +1,26 @@
+use indexmap::IndexMap;
 use schemars::JsonSchema;
 use serde::Deserialize;
 
-use crate::config::{ApplyLayer, ApplyValExt};
+use crate::config::{ApplyLayer, ApplyOptExt, ApplyValExt};
 
+mod auto_package_managers;
+mod files;
+mod installers;
+mod mirror;
 mod package_managers;
+mod run_hints;
+pub use auto_package_managers::{
+    AutoPackageManager, AutoPackageManagersConfig, AutoPackageManagersLayer, DetectedPackage,
+};
+pub use files::{ArtifactFileConfig, ArtifactPreference, ArtifactSystem, ArtifactTarget};
+pub use installers::InstallerConfig;
+pub use mirror::MirrorConfig;
 pub use package_managers::{PackageManagersConfig, PackageManagersLayer};
+pub use run_hints::{RunHintsConfig, RunHintsLayer, ScriptRunHintsConfig, ScriptRunHintsLayer};
 
-#[derive(Debug, Deserialize, JsonSchema)]
-#[serde(rename_all = "lowercase")]
-enum ArtifactSystem {
-    Windows,
-    Windows64,
-    WindowsArm,
-
-    Mac,
-    MacPpc,
-    Mac32,
-    MacSilicon,
-
-    Linux,
-    LinuxUbuntu,
-    LinuxDebian,
-    LinuxMandriva,
-    LinuxRedhat,
-    LinuxFedora,
-    LinuxSuse,
-    LinuxGentoo,
-
-    Ios,
-    Android,
-
-    Freebsd,
-}
+/// Where cargo-dist writes the dist-manifest.json for the release it's building
+pub const DEFAULT_DIST_MANIFEST_PATH: &str = "target/distrib/dist-manifest.json";
 
 /// Info about downloadable artifacts / installers / package-managers (cimplete version)
 #[derive(Debug)]
@@ -39,12 +28,61 @@ pub struct ArtifactsConfig {
     pub cargo_dist: bool,
     pub package_managers: PackageManagersConfig,
     pub hidden: Vec<String>,
+    /// Whether to show how many times each file has been downloaded
+    pub show_download_counts: bool,
+    /// Overrides for files matching globs (applied in order)
+    pub files: IndexMap<String, ArtifactFileConfig>,
+    /// Overrides for installers, keyed by their label
+    pub installers: IndexMap<String, InstallerConfig>,
+    /// How to tell people to run installer scripts
+    pub run_hints: RunHintsConfig,
+    /// The Homebrew tap that formulae in releases are published to (e.g. "axodotdev/tap")
+    pub homebrew_tap: Option<String>,
+    /// Which package managers to suggest based on the project's manifest
+    pub auto_package_managers: AutoPackageManagersConfig,
+    /// A local directory of artifacts to host with the site (e.g. "target/distrib")
+    pub local_dir: Option<String>,
+    /// Where to find the dist-manifest.json of a release that hasn't been published yet
+    pub dist_manifest_path: String,
+    /// A mirror to download files from instead of where they were published
+    pub mirror: Option<MirrorConfig>,
+    /// The app to show the artifacts of, for releases that contain several apps
+    pub app_name: Option<String>,
 }
 #[derive(Debug, Default, Deserialize, JsonSchema)]
 pub struct ArtifactsLayer {
     pub cargo_dist: Option<bool>,
     pub package_managers: Option<PackageManagersLayer>,
     pub hidden: Option<Vec<String>>,
+    /// Whether to show how many times each file has been downloaded
+    pub show_download_counts: Option<bool>,
+    /// Overrides for files matching globs (applied in order)
+    pub files: Option<IndexMap<String, ArtifactFileConfig>>,
+    /// Overrides for installers, keyed by their label
+    pub installers: Option<IndexMap<String, InstallerConfig>>,
+    /// How to tell people to run installer scripts
+    pub run_hints: Option<RunHintsLayer>,
+    /// The Homebrew tap that formulae in releases are published to (e.g. "axodotdev/tap")
+    pub homebrew_tap: Option<String>,
+    /// Which package managers to suggest based on the project's manifest
+    pub auto_package_managers: Option<AutoPackageManagersLayer>,
+    /// A local directory of artifacts to host with the site (e.g. "target/distrib")
+    ///
+    /// These are used for the release describing the current state of the project,
+    /// which is what we show if there's no forge to get releases from.
+    pub local_dir: Option<String>,
+    /// Where to find the dist-manifest.json of a release that hasn't been published yet
+    ///
+    /// If there are no releases, this lets you preview the installers of the upcoming one.
+    /// Defaults to where cargo-dist writes it ("target/distrib/dist-manifest.json").
+    pub dist_manifest_path: Option<String>,
+    /// A mirror to download files from instead of where they were published
+    pub mirror: Option<MirrorConfig>,
+    /// The app to show the artifacts of, for releases that contain several apps
+    ///
+    /// This is the name of the app in cargo-dist's dist-manifest.json, and otherwise
+    /// something that the names of the app's files contain. Defaults to the project's name.
+    pub app_name: Option<String>,
 }
 
 impl Default for ArtifactsConfig {
@@ -53,6 +91,16 @@ impl Default for ArtifactsConfig {
             cargo_dist: false,
             package_managers: PackageManagersConfig::default(),
             hidden: vec![],
+            show_download_counts: false,
+            files: IndexMap::default(),
+            installers: IndexMap::default(),
+            run_hints: RunHintsConfig::default(),
+            homebrew_tap: None,
+            auto_package_managers: AutoPackageManagersConfig::default(),
+            local_dir: None,
+            dist_manifest_path: DEFAULT_DIST_MANIFEST_PATH.to_owned(),
+            mirror: None,
+            app_name: None,
         }
     }
 }
@@ -64,16 +112,38 @@ impl ApplyLayer for ArtifactsConfig {
             cargo_dist,
             package_managers,
             hidden,
+            show_download_counts,
+            files,
+            installers,
+            run_hints,
+            homebrew_tap,
+            auto_package_managers,
+            local_dir,
+            dist_manifest_path,
+            mirror,
+            app_name,
         } = layer;
         self.cargo_dist.apply_val(cargo_dist);
         self.package_managers.apply_val_layer(package_managers);
         // In the future this might want to be `extend`
         self.hidden.apply_val(hidden);
+        self.show_download_counts.apply_val(show_download_counts);
+        // In the future this might want to be `extend`
+        self.files.apply_val(files);
+        self.installers.apply_val(installers);
+        self.run_hints.apply_val_layer(run_hints);
+        self.homebrew_tap.apply_opt(homebrew_tap);
+        self.auto_package_managers
+            .apply_val_layer(auto_package_managers);
+        self.local_dir.apply_opt(local_dir);
+        self.dist_manifest_path.apply_val(dist_manifest_path);
+        self.mirror.apply_opt(mirror);
+        self.app_name.apply_opt(app_name);
     }
 }
 
 impl ArtifactsConfig {
     pub fn has_some(&self) -> bool {
-        self.cargo_dist || !self.package_managers.is_empty()
+        self.cargo_dist || !self.package_managers.is_empty() || self.local_dir.is_some()
     }
 }
@@ -0,0 +1,62 @@
+use indexmap::IndexMap;
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+use crate::config::{ApplyLayer, ApplyOptExt, ApplyValExt};
+
+/// How to tell people to run installer scripts (complete version)
+#[derive(Debug, Default)]
+pub struct RunHintsConfig {
+    /// Run hints for shell (`.sh`) scripts
+    pub shell: ScriptRunHintsConfig,
+    /// Run hints for PowerShell (`.ps1`) scripts
+    pub powershell: ScriptRunHintsConfig,
+}
+/// How to tell people to run installer scripts (partial version used by oranda.json)
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct RunHintsLayer {
+    /// Run hints for shell (`.sh`) scripts
+    pub shell: Option<ScriptRunHintsLayer>,
+    /// Run hints for PowerShell (`.ps1`) scripts
+    pub powershell: Option<ScriptRunHintsLayer>,
+}
+
+/// How to tell people to run one kind of script (complete version)
+///
+/// Templates can use `{url}` (where to download the script) and `{file}` (the script's name).
+#[derive(Debug, Default)]
+pub struct ScriptRunHintsConfig {
+    /// The command to run the script, replacing the default one
+    pub template: Option<String>,
+    /// Other ways to run the script, shown as extra installers (label -> template)
+    pub variants: IndexMap<String, String>,
+}
+/// How to tell people to run one kind of script (partial version used by oranda.json)
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ScriptRunHintsLayer {
+    /// The command to run the script, replacing the default one
+    pub template: Option<String>,
+    /// Other ways to run the script, shown as extra installers (label -> template)
+    pub variants: Option<IndexMap<String, String>>,
+}
+
+impl ApplyLayer for RunHintsConfig {
+    type Layer = RunHintsLayer;
+    fn apply_layer(&mut self, layer: Self::Layer) {
+        // This is intentionally written slightly cumbersome to make you update this
+        let RunHintsLayer { shell, powershell } = layer;
+        self.shell.apply_val_layer(shell);
+        self.powershell.apply_val_layer(powershell);
+    }
+}
+
+impl ApplyLayer for ScriptRunHintsConfig {
+    type Layer = ScriptRunHintsLayer;
+    fn apply_layer(&mut self, layer: Self::Layer) {
+        // This is intentionally written slightly cumbersome to make you update this
+        let ScriptRunHintsLayer { template, variants } = layer;
+        self.template.apply_opt(template);
+        // In the future this might want to be `extend`
+        self.variants.apply_val(variants);
+    }
+}
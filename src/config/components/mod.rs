@@ -5,7 +5,12 @@ mod artifacts;
 mod funding;
 mod mdbooks;
 
-pub use artifacts::{ArtifactsConfig, ArtifactsLayer, PackageManagersConfig, PackageManagersLayer};
+pub use artifacts::{
+    ArtifactFileConfig, ArtifactPreference, ArtifactSystem, ArtifactTarget, ArtifactsConfig,
+    ArtifactsLayer, AutoPackageManager, AutoPackageManagersConfig, AutoPackageManagersLayer,
+    DetectedPackage, InstallerConfig, MirrorConfig, PackageManagersConfig, PackageManagersLayer,
+    RunHintsConfig, RunHintsLayer, ScriptRunHintsConfig, ScriptRunHintsLayer,
+};
 pub use funding::{FundingConfig, FundingLayer};
 pub use mdbooks::{MdBookConfig, MdBookLayer};
 
@@ -14,18 +14,25 @@ mod components;
 mod marketing;
 pub mod oranda_config;
 pub mod project;
+mod releases;
 pub mod style;
 
 pub use self::axoproject::AxoprojectConfig;
 pub use self::oranda_config::OrandaConfig;
 pub use builds::{BuildConfig, BuildLayer};
 pub use components::{
-    ArtifactsConfig, ArtifactsLayer, ComponentConfig, ComponentLayer, FundingConfig, FundingLayer,
-    MdBookConfig, MdBookLayer, PackageManagersConfig, PackageManagersLayer,
+    ArtifactFileConfig, ArtifactPreference, ArtifactSystem, ArtifactTarget, ArtifactsConfig,
+    ArtifactsLayer, AutoPackageManager, AutoPackageManagersConfig, AutoPackageManagersLayer,
+    ComponentConfig, ComponentLayer, DetectedPackage, FundingConfig, FundingLayer, InstallerConfig,
+    MdBookConfig, MdBookLayer, MirrorConfig, PackageManagersConfig, PackageManagersLayer,
+    RunHintsConfig, RunHintsLayer, ScriptRunHintsConfig, ScriptRunHintsLayer,
 };
 pub use marketing::{AnalyticsConfig, MarketingConfig, MarketingLayer, SocialConfig, SocialLayer};
 
 pub use project::{ProjectConfig, ProjectLayer};
+pub use releases::{
+    ReleaseChannelConfig, ReleaseSort, ReleasesConfig, ReleasesLayer, ReleasesSource,
+};
 pub use style::{StyleConfig, StyleLayer};
 
 /// Top-level mega-config
@@ -39,6 +46,8 @@ pub struct Config {
     pub marketing: MarketingConfig,
     /// Info about layout/themes
     pub styles: StyleConfig,
+    /// Info about where releases come from
+    pub releases: ReleasesConfig,
     /// Additional optional components
     pub components: ComponentConfig,
 }
@@ -92,11 +101,13 @@ impl Config {
             let AxoprojectConfig {
                 project,
                 cargo_dist,
+                packages,
             } = layer;
 
             self.project.apply_layer(project);
             if let Some(artifacts) = &mut self.components.artifacts {
                 artifacts.cargo_dist.apply_val(cargo_dist);
+                artifacts.auto_package_managers.detected = packages;
             }
         }
     }
@@ -110,12 +121,14 @@ impl Config {
                 build,
                 marketing,
                 styles,
+                releases,
                 components,
             } = layer;
             self.project.apply_val_layer(project);
             self.build.apply_val_layer(build);
             self.marketing.apply_val_layer(marketing);
             self.styles.apply_val_layer(styles);
+            self.releases.apply_val_layer(releases);
             self.components.apply_val_layer(components);
         }
     }
@@ -136,6 +149,7 @@ impl Default for Config {
             build: BuildConfig::default(),
             marketing: MarketingConfig::default(),
             styles: StyleConfig::default(),
+            releases: ReleasesConfig::default(),
             components: ComponentConfig::default(),
         }
     }
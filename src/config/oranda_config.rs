@@ -6,7 +6,7 @@ use serde::Deserialize;
 use crate::errors::*;
 use crate::message::{Message, MessageType};
 
-use super::{BuildLayer, ComponentLayer, MarketingLayer, ProjectLayer, StyleLayer};
+use super::{BuildLayer, ComponentLayer, MarketingLayer, ProjectLayer, ReleasesLayer, StyleLayer};
 
 #[derive(Debug, Deserialize, JsonSchema)]
 pub struct OrandaConfig {
@@ -18,6 +18,8 @@ pub struct OrandaConfig {
     pub marketing: Option<MarketingLayer>,
     /// Info about layout/themes
     pub styles: Option<StyleLayer>,
+    /// Info about where releases come from
+    pub releases: Option<ReleasesLayer>,
     /// Additional optional components
     pub components: Option<ComponentLayer>,
 }
@@ -0,0 +1,131 @@
+use indexmap::IndexMap;
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+use super::{ApplyLayer, ApplyOptExt, ApplyValExt};
+
+/// Where oranda should look for releases
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReleasesSource {
+    /// Github Releases of the project's `repository`
+    Github,
+    /// Tags in the local git repository
+    GitTags,
+}
+
+/// How releases should be ordered
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReleaseSort {
+    /// Newest publish date first
+    Date,
+    /// Highest semver version (parsed from the tag) first
+    Semver,
+}
+
+/// Which releases belong to a release channel (e.g. "stable" or "nightly")
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema)]
+pub struct ReleaseChannelConfig {
+    /// Only include releases that are (`true`) or aren't (`false`) prereleases
+    ///
+    /// If this is unset, both are included.
+    pub prerelease: Option<bool>,
+    /// Globs for the tags of releases in this channel (e.g. `nightly-*`)
+    ///
+    /// If this is empty, releases with any tag are included.
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+/// Info about where releases come from (complete version)
+#[derive(Debug)]
+pub struct ReleasesConfig {
+    /// Where to get releases from
+    ///
+    /// If this is None, we use Github Releases if a `repository` is set,
+    /// and otherwise just describe the current state of the project.
+    pub source: Option<ReleasesSource>,
+    /// A glob that local git tags must match to be considered releases (e.g. `v*`)
+    ///
+    /// Only used by the `git_tags` source.
+    pub git_tag_pattern: String,
+    /// Globs for the tags of releases we should use
+    ///
+    /// If this is empty, all releases are used.
+    pub include: Vec<String>,
+    /// Globs for the tags of releases we should ignore (e.g. `css-v*`)
+    pub exclude: Vec<String>,
+    /// How releases should be ordered when picking the latest one
+    pub sort: ReleaseSort,
+    /// Release channels to offer the latest release of (e.g. "stable", "beta" and "nightly")
+    ///
+    /// If this is empty, we only offer the latest release.
+    pub channels: IndexMap<String, ReleaseChannelConfig>,
+}
+/// Info about where releases come from (partial version used by oranda.json)
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ReleasesLayer {
+    /// Where to get releases from
+    ///
+    /// If this is unset, we use Github Releases if a `repository` is set,
+    /// and otherwise just describe the current state of the project.
+    pub source: Option<ReleasesSource>,
+    /// A glob that local git tags must match to be considered releases (e.g. `v*`)
+    ///
+    /// Only used by the `git_tags` source.
+    pub git_tag_pattern: Option<String>,
+    /// Globs for the tags of releases we should use
+    ///
+    /// If this is empty, all releases are used.
+    pub include: Option<Vec<String>>,
+    /// Globs for the tags of releases we should ignore (e.g. `css-v*`)
+    pub exclude: Option<Vec<String>>,
+    /// How releases should be ordered when picking the latest one
+    pub sort: Option<ReleaseSort>,
+    /// Release channels to offer the latest release of (e.g. "stable", "beta" and "nightly")
+    ///
+    /// If this is empty, we only offer the latest release.
+    pub channels: Option<IndexMap<String, ReleaseChannelConfig>>,
+}
+
+impl Default for ReleasesConfig {
+    fn default() -> Self {
+        ReleasesConfig {
+            source: None,
+            git_tag_pattern: "*".to_owned(),
+            include: vec![],
+            exclude: vec![],
+            sort: ReleaseSort::Date,
+            channels: IndexMap::new(),
+        }
+    }
+}
+impl ApplyLayer for ReleasesConfig {
+    type Layer = ReleasesLayer;
+    fn apply_layer(&mut self, layer: Self::Layer) {
+        // This is intentionally written slightly cumbersome to make you update this
+        let ReleasesLayer {
+            source,
+            git_tag_pattern,
+            include,
+            exclude,
+            sort,
+            channels,
+        } = layer;
+        self.source.apply_opt(source);
+        self.git_tag_pattern.apply_val(git_tag_pattern);
+        // In the future these might want to be `extend`
+        self.include.apply_val(include);
+        self.exclude.apply_val(exclude);
+        self.sort.apply_val(sort);
+        self.channels.apply_val(channels);
+    }
+}
+
+impl ReleasesConfig {
+    /// Whether the user has asked for only some of their releases to be used
+    pub fn has_filters(&self) -> bool {
+        !self.include.is_empty() || !self.exclude.is_empty()
+    }
+}
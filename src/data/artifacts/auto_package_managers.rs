@@ -0,0 +1,89 @@
+//! Logic for suggesting package managers based on the project's manifest
+//!
+//! If the project is a Rust package with binaries, people can `cargo install` it, if it's an npm
+//! package with a `bin` they can `npm install -g` it, and so on. We pin these to the version of
+//! the release, so each release's page suggests installing that release.
+
+use crate::config::{AutoPackageManager, DetectedPackage};
+
+use super::*;
+
+impl ReleaseArtifacts {
+    /// Add installers for the package managers the project can be installed with
+    ///
+    /// Package managers the user already suggests in `package_managers` (or that cargo-dist
+    /// suggested) are skipped.
+    pub fn add_auto_package_managers(
+        &mut self,
+        config: &ArtifactsConfig,
+        version: Option<&semver::Version>,
+    ) {
+        for package in config.auto_package_managers.enabled() {
+            if self.already_suggests(config, package.manager) {
+                continue;
+            }
+            let run_hint = auto_run_hint(package, version);
+            let mut installer = simple_run_installer(package.manager.label(), &run_hint);
+            installer.display = DisplayPreference::Preferred;
+            self.add_installer(installer);
+        }
+    }
+
+    /// Whether we're already suggesting this package manager some other way
+    fn already_suggests(&self, config: &ArtifactsConfig, manager: AutoPackageManager) -> bool {
+        let label = manager.label();
+        let command = match manager {
+            AutoPackageManager::Cargo => "cargo install",
+            AutoPackageManager::Binstall => "cargo binstall",
+            AutoPackageManager::Npm => "npm i",
+            AutoPackageManager::Npx => "npx ",
+            AutoPackageManager::Pipx => "pipx install",
+        };
+        let user_suggests = config.package_managers.has(label)
+            || config
+                .package_managers
+                .preferred
+                .values()
+                .chain(config.package_managers.additional.values())
+                .any(|script| script.contains(command));
+        let is_npm = matches!(manager, AutoPackageManager::Npm | AutoPackageManager::Npx);
+        user_suggests
+            || (is_npm && config.package_managers.has_npm())
+            || self
+                .installers
+                .iter()
+                .any(|installer| installer.label == label)
+    }
+}
+
+/// Get the command to install a package with its package manager
+fn auto_run_hint(package: &DetectedPackage, version: Option<&semver::Version>) -> String {
+    let name = &package.name;
+    match (package.manager, version) {
+        (AutoPackageManager::Cargo, Some(version)) => {
+            format!("cargo install {name} --version {version} --locked")
+        }
+        (AutoPackageManager::Cargo, None) => format!("cargo install {name} --locked"),
+        (AutoPackageManager::Binstall, Some(version)) => format!("cargo binstall {name}@{version}"),
+        (AutoPackageManager::Binstall, None) => format!("cargo binstall {name}"),
+        (AutoPackageManager::Npm, Some(version)) => format!("npm install -g {name}@{version}"),
+        (AutoPackageManager::Npm, None) => format!("npm install -g {name}"),
+        (AutoPackageManager::Npx, version) => {
+            let package_spec = match version {
+                Some(version) => format!("{name}@{version}"),
+                None => name.clone(),
+            };
+            // npx runs the binary named after the package (without its @scope/),
+            // so anything else has to be asked for explicitly
+            let unscoped_name = name.rsplit('/').next().unwrap_or(name);
+            match package.binaries.first() {
+                Some(binary) if binary != unscoped_name || package.binaries.len() > 1 => {
+                    format!("npx --package {package_spec} {binary}")
+                }
+                _ => format!("npx {package_spec}"),
+            }
+        }
+        (AutoPackageManager::Pipx, Some(version)) => format!("pipx install {name}=={version}"),
+        (AutoPackageManager::Pipx, None) => format!("pipx install {name}"),
+    }
+}
@@ -0,0 +1,148 @@
+//! Logic for finding checksums of files
+//!
+//! Checksums can come from per-file checksum files (`my-app.tar.gz.sha256`, which cargo-dist makes),
+//! or from aggregate files listing the checksums of many files (`SHA256SUMS`, `checksums.txt`).
+
+use super::*;
+
+/// Extensions of files containing the SHA-256 checksum of a single file
+const EXTS_FOR_SHA256: &[&str] = &[".sha256", ".sha256sum"];
+/// Names (or suffixes of names) of files containing the SHA-256 checksums of many files
+const AGGREGATE_SHA256_NAMES: &[&str] = &[
+    "SHA256SUMS",
+    "SHA256SUMS.txt",
+    "sha256sums.txt",
+    "checksums.txt",
+];
+
+impl ReleaseArtifacts {
+    /// Find files that are checksums of other files, and link them up
+    ///
+    /// Checksum files are also excluded from any further inference.
+    pub(crate) fn link_checksum_files(&mut self) {
+        for file_idx in self.file_indices() {
+            let file = self.file(file_idx);
+            if is_aggregate_checksum_file(&file.name) {
+                // We can't know what this covers until we fetch it
+                self.file_mut(file_idx).infer = false;
+                continue;
+            }
+            let Some(checksummed_name) = EXTS_FOR_SHA256
+                .iter()
+                .find_map(|ext| file.name.strip_suffix(ext))
+            else {
+                continue;
+            };
+            let checksummed_name = checksummed_name.to_owned();
+            self.file_mut(file_idx).infer = false;
+            if let Some(checksummed) = self.file_idx(&checksummed_name) {
+                let checksummed = self.file_mut(checksummed);
+                if checksummed.checksum_file.is_none() {
+                    checksummed.checksum_file = Some(file_idx);
+                }
+            }
+        }
+    }
+
+    /// Fetch checksum files to get the actual SHA-256 of files
+    ///
+    /// Failing to get a checksum is never fatal, we just won't show it.
+    pub fn fetch_checksums(&mut self, config: &Config) -> Result<()> {
+        let cache = HttpCache::new(config.build.cache_mode);
+        let mut failed = vec![];
+
+        // Aggregate files can fill in checksums for everything they mention
+        let aggregates: Vec<FileIdx> = self
+            .file_indices()
+            .filter(|idx| is_aggregate_checksum_file(&self.file(*idx).name))
+            .collect();
+        for aggregate_idx in aggregates {
+            let aggregate = self.file(aggregate_idx);
+            let contents = tokio::runtime::Handle::current()
+                .block_on(self.fetch_file_text(aggregate_idx, &cache));
+            let contents = match contents {
+                Ok(contents) => contents,
+                Err(e) => {
+                    failed.push((aggregate.name.clone(), e));
+                    continue;
+                }
+            };
+            for (sha256, name) in parse_checksums(&contents) {
+                let Some(file_idx) = self.file_idx(&name.to_owned()) else {
+                    continue;
+                };
+                let file = self.file_mut(file_idx);
+                file.sha256 = Some(sha256.to_owned());
+                if file.checksum_file.is_none() {
+                    file.checksum_file = Some(aggregate_idx);
+                }
+            }
+        }
+
+        // Then fetch the per-file checksums for anything still missing
+        for file_idx in self.file_indices() {
+            let file = self.file(file_idx);
+            let Some(checksum_idx) = file.checksum_file else {
+                continue;
+            };
+            let checksum = self.file(checksum_idx);
+            if file.sha256.is_some()
+                || !EXTS_FOR_SHA256
+                    .iter()
+                    .any(|ext| checksum.name.ends_with(ext))
+            {
+                continue;
+            }
+            let contents = tokio::runtime::Handle::current()
+                .block_on(self.fetch_file_text(checksum_idx, &cache));
+            match contents {
+                Ok(contents) => {
+                    let sha256 = parse_checksums(&contents)
+                        .next()
+                        .map(|(sha256, _)| sha256.to_owned())
+                        .or_else(|| {
+                            // Some tools write just the hash, with no file name
+                            let hash = contents.trim();
+                            is_sha256(hash).then(|| hash.to_owned())
+                        });
+                    self.file_mut(file_idx).sha256 = sha256;
+                }
+                Err(e) => failed.push((checksum.name.clone(), e)),
+            }
+        }
+
+        if !failed.is_empty() {
+            for (name, e) in &failed {
+                tracing::warn!("Failed to fetch checksum file {name}: {e}");
+            }
+            let names: Vec<&str> = failed.iter().map(|(name, _)| name.as_str()).collect();
+            let msg = format!(
+                "Couldn't fetch some checksum files ({}), so their checksums won't be shown.",
+                names.join(", ")
+            );
+            Message::new(MessageType::Warning, &msg).print();
+        }
+        Ok(())
+    }
+}
+
+/// Whether this file looks like a list of SHA-256 checksums for many files
+fn is_aggregate_checksum_file(name: &str) -> bool {
+    AGGREGATE_SHA256_NAMES
+        .iter()
+        .any(|aggregate| name == *aggregate || name.ends_with(&format!("_{aggregate}")))
+}
+
+/// Parse the output of `sha256sum` (lines of `<hash>  <name>`, or `<hash> *<name>` for binary mode)
+fn parse_checksums(contents: &str) -> impl Iterator<Item = (&str, &str)> {
+    contents.lines().filter_map(|line| {
+        let (hash, name) = line.trim().split_once(char::is_whitespace)?;
+        let name = name.trim_start();
+        let name = name.strip_prefix('*').unwrap_or(name);
+        (is_sha256(hash) && !name.is_empty()).then_some((hash, name))
+    })
+}
+
+fn is_sha256(hash: &str) -> bool {
+    hash.len() == 64 && hash.chars().all(|c| c.is_ascii_hexdigit())
+}
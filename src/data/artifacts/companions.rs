@@ -0,0 +1,106 @@
+//! Logic for finding files that vouch for other files
+//!
+//! These are signatures (`.sig`, `.asc`, `.minisig`), sigstore/cosign bundles, SBOMs (`.cdx.json`)
+//! and provenance attestations (`.intoto.jsonl`). They're named after the file they're for
+//! (`my-app.tar.gz.minisig`), so we link them up like checksum files, and never treat them as
+//! installers.
+
+use super::*;
+
+/// Extensions of companion files, and what kind of companion they are
+///
+/// Longer extensions must come before any extension they end with.
+const COMPANION_EXTS: &[(&str, CompanionKind)] = &[
+    (".sigstore.json", CompanionKind::SigstoreBundle),
+    (".sigstore", CompanionKind::SigstoreBundle),
+    (".bundle", CompanionKind::SigstoreBundle),
+    (".minisig", CompanionKind::Minisign),
+    (".asc", CompanionKind::Gpg),
+    (".sig", CompanionKind::Gpg),
+    (".cdx.json", CompanionKind::Sbom),
+    (".cdx.xml", CompanionKind::Sbom),
+    (".spdx.json", CompanionKind::Sbom),
+    (".intoto.jsonl", CompanionKind::Provenance),
+];
+
+/// Extension of the certificates cosign makes for keyless `.sig` signatures
+const COSIGN_CERT_EXT: &str = ".pem";
+
+/// A file that vouches for another file
+#[derive(Debug, Copy, Clone, Serialize)]
+pub struct Companion {
+    /// What kind of file it is
+    pub kind: CompanionKind,
+    /// The file
+    pub file: FileIdx,
+}
+
+/// The different kinds of companion files
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize)]
+pub enum CompanionKind {
+    /// A detached GPG signature (`.asc` or `.sig`)
+    Gpg,
+    /// A minisign signature (`.minisig`)
+    Minisign,
+    /// A signature made with `cosign sign-blob`, along with its certificate (`.sig` + `.pem`)
+    Cosign {
+        /// The certificate for the signature
+        certificate: FileIdx,
+    },
+    /// A sigstore bundle made with `cosign sign-blob --bundle`
+    SigstoreBundle,
+    /// A software bill of materials
+    Sbom,
+    /// A SLSA provenance attestation
+    Provenance,
+}
+
+impl CompanionKind {
+    /// A short name for this kind of file
+    pub fn label(&self) -> &'static str {
+        match self {
+            CompanionKind::Gpg => "GPG signature",
+            CompanionKind::Minisign => "minisign signature",
+            CompanionKind::Cosign { .. } => "cosign signature",
+            CompanionKind::SigstoreBundle => "sigstore bundle",
+            CompanionKind::Sbom => "SBOM",
+            CompanionKind::Provenance => "provenance",
+        }
+    }
+}
+
+impl ReleaseArtifacts {
+    /// Find signatures/SBOMs/attestations of other files, and link them up
+    ///
+    /// Companion files (and cosign certificates) are also excluded from any further inference,
+    /// even if we can't find the file they're for.
+    pub(crate) fn link_companion_files(&mut self) {
+        for file_idx in self.file_indices() {
+            let file = self.file(file_idx);
+            let Some((companion_name, ext, kind)) = COMPANION_EXTS.iter().find_map(|(ext, kind)| {
+                let name = file.name.strip_suffix(ext)?;
+                Some((name.to_owned(), *ext, *kind))
+            }) else {
+                continue;
+            };
+            self.file_mut(file_idx).infer = false;
+
+            // A `.sig` with a matching certificate was made by cosign, not gpg
+            let mut kind = kind;
+            if ext == ".sig" {
+                let cert_name = format!("{companion_name}{COSIGN_CERT_EXT}");
+                if let Some(certificate) = self.file_idx(&cert_name) {
+                    self.file_mut(certificate).infer = false;
+                    kind = CompanionKind::Cosign { certificate };
+                }
+            }
+
+            if let Some(companioned) = self.file_idx(&companion_name) {
+                self.file_mut(companioned).companion_files.push(Companion {
+                    kind,
+                    file: file_idx,
+                });
+            }
+        }
+    }
+}
@@ -1,6 +1,8 @@
 use camino::Utf8PathBuf;
 
+use super::run_hints::{render_run_hint, ScriptKind};
 use super::*;
+use crate::config::{ArtifactSystem, ArtifactTarget};
 
 // Architectures
 // const ARCH_X86: &str = "i686";
@@ -28,30 +30,75 @@ const KNOWN_MAC_TARGETS: &[&Targ] = &[TARGET_X86_MAC, TARGET_X64_MAC, TARGET_ARM
 const TARGET_X86_LINUX_GNU: &Targ = "i686-unknown-linux-gnu";
 const TARGET_X64_LINUX_GNU: &Targ = "x86_64-unknown-linux-gnu";
 const TARGET_ARM64_LINUX_GNU: &Targ = "aarch64-unknown-linux-gnu";
+const TARGET_ARMV7_LINUX_GNU: &Targ = "armv7-unknown-linux-gnueabihf";
+const TARGET_RISCV64_LINUX_GNU: &Targ = "riscv64gc-unknown-linux-gnu";
 const KNOWN_LINUX_GNU_TARGETS: &[&Targ] = &[
     TARGET_X86_LINUX_GNU,
     TARGET_X64_LINUX_GNU,
     TARGET_ARM64_LINUX_GNU,
+    TARGET_ARMV7_LINUX_GNU,
+    TARGET_RISCV64_LINUX_GNU,
 ];
 
 const TARGET_X86_LINUX_MUSL: &Targ = "i686-unknown-linux-musl";
 const TARGET_X64_LINUX_MUSL: &Targ = "x86_64-unknown-linux-musl";
 const TARGET_ARM64_LINUX_MUSL: &Targ = "aarch64-unknown-linux-musl";
+const TARGET_ARMV7_LINUX_MUSL: &Targ = "armv7-unknown-linux-musleabihf";
 const KNOWN_LINUX_MUSL_TARGETS: &[&Targ] = &[
     TARGET_X86_LINUX_MUSL,
     TARGET_X64_LINUX_MUSL,
     TARGET_ARM64_LINUX_MUSL,
+    TARGET_ARMV7_LINUX_MUSL,
 ];
 
 const KNOWN_LINUX_TARGETS: &[&[&Targ]] = &[KNOWN_LINUX_GNU_TARGETS, KNOWN_LINUX_MUSL_TARGETS];
 
+const TARGET_ARM64_ANDROID: &Targ = "aarch64-linux-android";
+const KNOWN_ANDROID_TARGETS: &[&Targ] = &[TARGET_ARM64_ANDROID];
+
+const TARGET_X64_FREEBSD: &Targ = "x86_64-unknown-freebsd";
+const TARGET_ARM64_FREEBSD: &Targ = "aarch64-unknown-freebsd";
+const TARGET_X64_NETBSD: &Targ = "x86_64-unknown-netbsd";
+const TARGET_X64_ILLUMOS: &Targ = "x86_64-unknown-illumos";
+const KNOWN_OTHER_UNIX_TARGETS: &[&Targ] = &[
+    TARGET_X64_FREEBSD,
+    TARGET_ARM64_FREEBSD,
+    TARGET_X64_NETBSD,
+    TARGET_X64_ILLUMOS,
+];
+
+const TARGET_WASM32_WASI: &Targ = "wasm32-wasi";
+const KNOWN_WASM_TARGETS: &[&Targ] = &[TARGET_WASM32_WASI];
+
 pub const KNOWN_TARGET_TRIPLES: &[&[&Targ]] = &[
     KNOWN_WINDOWS_TARGETS,
     KNOWN_MAC_TARGETS,
     KNOWN_LINUX_GNU_TARGETS,
     KNOWN_LINUX_MUSL_TARGETS,
+    KNOWN_ANDROID_TARGETS,
+    KNOWN_OTHER_UNIX_TARGETS,
+    KNOWN_WASM_TARGETS,
 ];
 
+// Aliases that tools like goreleaser use for platforms instead of target triples
+// (e.g. `my-app_1.2.0_linux_amd64.tar.gz`), these are compared to `-`/`_`/`.`-separated
+// parts of the (lowercased) file name
+const OS_ALIASES_LINUX: &[&str] = &["linux"];
+const OS_ALIASES_MAC: &[&str] = &["darwin", "macos"];
+const OS_ALIASES_WINDOWS: &[&str] = &["windows"];
+const OS_ALIASES_ANDROID: &[&str] = &["android"];
+const OS_ALIASES_FREEBSD: &[&str] = &["freebsd"];
+const OS_ALIASES_NETBSD: &[&str] = &["netbsd"];
+const OS_ALIASES_ILLUMOS: &[&str] = &["illumos"];
+const ARCH_ALIASES_X86: &[&str] = &["386", "i386", "i686"];
+const ARCH_ALIASES_X64: &[&str] = &["amd64", "x86_64"];
+const ARCH_ALIASES_ARM64: &[&str] = &["arm64", "aarch64"];
+const ARCH_ALIASES_ARMV7: &[&str] = &["armv7", "armv7l"];
+const ARCH_ALIASES_RISCV64: &[&str] = &["riscv64"];
+// goreleaser's name for universal macOS binaries
+const ARCH_ALIASES_MAC_UNIVERSAL: &[&str] = &["all", "universal"];
+const LIBC_ALIASES_MUSL: &[&str] = &["musl"];
+
 // Various extensions for known archive formats
 const EXTS_FOR_TAR_BZIP2: &[&str] = &[".tar.bz2", ".tb2", ".tbz", ".tbz2", ".tz2"];
 const EXTS_FOR_TAR_GZIP: &[&str] = &[".tar.gz", ".taz", ".tgz"];
@@ -124,8 +171,12 @@ pub(crate) const KNOWN_SCRIPT_EXTS: &[&str] = &[EXT_SCRIPT_SHELL, EXT_SCRIPT_POW
 impl ReleaseArtifacts {
     /// Infer installers/artifacts based solely on file names
     pub fn add_inference(&mut self) {
-        // Gotta clone this upfront to avoid borrowing stuff
-        let app_name = self.app_name.clone();
+        // Checksum files and signatures aren't installers, but we want to know what they're for
+        self.link_checksum_files();
+        self.link_companion_files();
+
+        // Gotta get this upfront to avoid borrowing stuff
+        let app_name = self.app_filter();
         for file_idx in self.file_indices() {
             let file = self.file_mut(file_idx);
             // Skip this
@@ -147,6 +198,10 @@ impl ReleaseArtifacts {
                     targets.push(target.to_owned());
                 }
             }
+            // Otherwise try platform names like "linux_amd64"
+            if targets.is_empty() {
+                targets = infer_targets_from_aliases(file);
+            }
 
             let label;
             let description = String::new();
@@ -216,6 +271,143 @@ impl ReleaseArtifacts {
     }
 }
 
+/// Given a file that doesn't contain a target triple, look for platform aliases like "linux_amd64"
+/// or "darwin-arm64" in its name and turn them into target triples
+fn infer_targets_from_aliases(file: &File) -> Vec<TargetTriple> {
+    // "x86_64" would otherwise be split in two
+    let name = file.name.to_lowercase().replace("x86_64", "amd64");
+    let parts: Vec<&str> = name.split(['-', '_', '.']).collect();
+    let has = |aliases: &[&str]| parts.iter().any(|part| aliases.contains(part));
+
+    let mut targets = vec![];
+    // Android also says "linux" sometimes, so it has to go first
+    if has(OS_ALIASES_ANDROID) {
+        if has(ARCH_ALIASES_ARM64) {
+            targets.push(TARGET_ARM64_ANDROID);
+        }
+    } else if has(OS_ALIASES_LINUX) {
+        let musl = has(LIBC_ALIASES_MUSL);
+        if has(ARCH_ALIASES_X86) {
+            targets.push(if musl {
+                TARGET_X86_LINUX_MUSL
+            } else {
+                TARGET_X86_LINUX_GNU
+            });
+        }
+        if has(ARCH_ALIASES_X64) {
+            targets.push(if musl {
+                TARGET_X64_LINUX_MUSL
+            } else {
+                TARGET_X64_LINUX_GNU
+            });
+        }
+        if has(ARCH_ALIASES_ARM64) {
+            targets.push(if musl {
+                TARGET_ARM64_LINUX_MUSL
+            } else {
+                TARGET_ARM64_LINUX_GNU
+            });
+        }
+        if has(ARCH_ALIASES_ARMV7) {
+            targets.push(if musl {
+                TARGET_ARMV7_LINUX_MUSL
+            } else {
+                TARGET_ARMV7_LINUX_GNU
+            });
+        }
+        if has(ARCH_ALIASES_RISCV64) && !musl {
+            targets.push(TARGET_RISCV64_LINUX_GNU);
+        }
+    } else if has(OS_ALIASES_MAC) {
+        if has(ARCH_ALIASES_X86) {
+            targets.push(TARGET_X86_MAC);
+        }
+        if has(ARCH_ALIASES_X64) || has(ARCH_ALIASES_MAC_UNIVERSAL) {
+            targets.push(TARGET_X64_MAC);
+        }
+        if has(ARCH_ALIASES_ARM64) || has(ARCH_ALIASES_MAC_UNIVERSAL) {
+            targets.push(TARGET_ARM64_MAC);
+        }
+    } else if has(OS_ALIASES_WINDOWS) {
+        if has(ARCH_ALIASES_X86) {
+            targets.push(TARGET_X86_WINDOWS);
+        }
+        if has(ARCH_ALIASES_X64) {
+            targets.push(TARGET_X64_WINDOWS);
+        }
+        if has(ARCH_ALIASES_ARM64) {
+            targets.push(TARGET_ARM64_WINDOWS);
+        }
+    } else if has(OS_ALIASES_FREEBSD) {
+        if has(ARCH_ALIASES_X64) {
+            targets.push(TARGET_X64_FREEBSD);
+        }
+        if has(ARCH_ALIASES_ARM64) {
+            targets.push(TARGET_ARM64_FREEBSD);
+        }
+    } else if has(OS_ALIASES_NETBSD) {
+        if has(ARCH_ALIASES_X64) {
+            targets.push(TARGET_X64_NETBSD);
+        }
+    } else if has(OS_ALIASES_ILLUMOS) && has(ARCH_ALIASES_X64) {
+        targets.push(TARGET_X64_ILLUMOS);
+    }
+    targets.into_iter().map(|t| t.to_owned()).collect()
+}
+
+/// Get the target triples for a platform named in the config
+pub fn targets_for_config(target: &ArtifactTarget) -> Vec<TargetTriple> {
+    match target {
+        ArtifactTarget::System(system) => targets_for_system(*system),
+        ArtifactTarget::Triple(triple) => vec![triple.clone()],
+    }
+}
+
+/// Get the target triples for a system named in the config
+///
+/// Linux distros can't be told apart by target triple, so they're the same as "linux".
+pub fn targets_for_system(system: ArtifactSystem) -> Vec<TargetTriple> {
+    let flat = |lists: &[&[&Targ]]| -> Vec<TargetTriple> {
+        lists
+            .iter()
+            .copied()
+            .flatten()
+            .map(|t| (*t).to_owned())
+            .collect()
+    };
+    match system {
+        ArtifactSystem::Windows => flat(&[KNOWN_WINDOWS_TARGETS]),
+        ArtifactSystem::Windows64 => flat(&[&[TARGET_X64_WINDOWS]]),
+        ArtifactSystem::WindowsArm => flat(&[&[TARGET_ARM64_WINDOWS]]),
+
+        ArtifactSystem::Mac => flat(&[KNOWN_MAC_TARGETS]),
+        ArtifactSystem::MacPpc => flat(&[&["powerpc-apple-darwin"]]),
+        ArtifactSystem::Mac32 => flat(&[&[TARGET_X86_MAC]]),
+        ArtifactSystem::Mac64 => flat(&[&[TARGET_X64_MAC]]),
+        ArtifactSystem::MacSilicon => flat(&[&[TARGET_ARM64_MAC]]),
+
+        ArtifactSystem::Linux
+        | ArtifactSystem::LinuxUbuntu
+        | ArtifactSystem::LinuxDebian
+        | ArtifactSystem::LinuxMandriva
+        | ArtifactSystem::LinuxRedhat
+        | ArtifactSystem::LinuxFedora
+        | ArtifactSystem::LinuxSuse
+        | ArtifactSystem::LinuxGentoo => flat(KNOWN_LINUX_TARGETS),
+        ArtifactSystem::Linux64 => flat(&[&[TARGET_X64_LINUX_GNU, TARGET_X64_LINUX_MUSL]]),
+        ArtifactSystem::LinuxArm => flat(&[&[TARGET_ARM64_LINUX_GNU, TARGET_ARM64_LINUX_MUSL]]),
+
+        ArtifactSystem::Ios => flat(&[&["aarch64-apple-ios"]]),
+        ArtifactSystem::Android => flat(&[KNOWN_ANDROID_TARGETS]),
+
+        ArtifactSystem::Freebsd => flat(&[&[TARGET_X64_FREEBSD, TARGET_ARM64_FREEBSD]]),
+        ArtifactSystem::Netbsd => flat(&[&[TARGET_X64_NETBSD]]),
+        ArtifactSystem::Illumos => flat(&[&[TARGET_X64_ILLUMOS]]),
+
+        ArtifactSystem::Wasi => flat(&[KNOWN_WASM_TARGETS]),
+    }
+}
+
 /// Given a file that appears to be a "bundle" but doesn't specify a target,
 /// infer the targets it applies to
 fn infer_targets_for_bundle(file: &File) -> Vec<TargetTriple> {
@@ -276,18 +468,12 @@ fn infer_targets_for_script(file: &File) -> Vec<TargetTriple> {
 
 /// Infer the command to curl|sh a script
 fn infer_run_hint_for_script(file: &File) -> String {
-    if file.name.ends_with(EXT_SCRIPT_POWERSHELL) {
-        format!(
-            "curl --proto '=https' --tlsv1.2 -LsSf {} | sh",
-            file.download_url
-        )
-    } else if file.name.ends_with(EXT_SCRIPT_SHELL) {
-        format!("irm {} | iex", file.download_url)
-    } else {
+    let Some(kind) = ScriptKind::for_file(file) else {
         unimplemented!(
             "Looks like someone added a new kind of script but didn't add a run hint for it?"
         );
-    }
+    };
+    render_run_hint(kind.default_template(), file)
 }
 
 /// Infer the label for a bundle
@@ -332,10 +518,22 @@ pub fn triple_to_display_name(name: &str) -> Option<&str> {
         TARGET_X86_LINUX_GNU => Some("x86 Linux"),
         TARGET_X64_LINUX_GNU => Some("x64 Linux"),
         TARGET_ARM64_LINUX_GNU => Some("arm64 Linux"),
+        TARGET_ARMV7_LINUX_GNU => Some("armv7 Linux"),
+        TARGET_RISCV64_LINUX_GNU => Some("riscv64 Linux"),
 
         TARGET_X86_LINUX_MUSL => Some("x86 musl Linux"),
         TARGET_X64_LINUX_MUSL => Some("x64 musl Linux"),
         TARGET_ARM64_LINUX_MUSL => Some("arm64 musl Linux"),
+        TARGET_ARMV7_LINUX_MUSL => Some("armv7 musl Linux"),
+
+        TARGET_ARM64_ANDROID => Some("arm64 Android"),
+
+        TARGET_X64_FREEBSD => Some("x64 FreeBSD"),
+        TARGET_ARM64_FREEBSD => Some("arm64 FreeBSD"),
+        TARGET_X64_NETBSD => Some("x64 NetBSD"),
+        TARGET_X64_ILLUMOS => Some("x64 illumos"),
+
+        TARGET_WASM32_WASI => Some("WebAssembly (WASI)"),
 
         TARGET_X86_WINDOWS => Some("x86 Windows"),
         TARGET_X64_WINDOWS => Some("x64 Windows"),
@@ -350,3 +548,47 @@ pub fn triple_to_display_name(name: &str) -> Option<&str> {
         _ => None,
     }
 }
+
+/// Get a display name for any target triple, even ones we don't know about
+///
+/// Unknown triples get a best-effort name built from their parts
+/// (e.g. "powerpc64le-unknown-linux-gnu" is "powerpc64le Linux").
+pub fn triple_to_readable_name(name: &str) -> String {
+    if let Some(display_name) = triple_to_display_name(name) {
+        return display_name.to_owned();
+    }
+    let name = name.trim();
+    let Some((arch, rest)) = name.split_once('-') else {
+        return name.to_owned();
+    };
+    let arch = match arch {
+        "x86_64" => "x64",
+        "i686" | "i586" => "x86",
+        "aarch64" => "arm64",
+        _ => arch,
+    };
+    let os = if rest.contains("windows") {
+        "Windows"
+    } else if rest.contains("darwin") {
+        "macOS"
+    } else if rest.contains("android") {
+        "Android"
+    } else if rest.contains("linux-musl") {
+        "musl Linux"
+    } else if rest.contains("linux") {
+        "Linux"
+    } else if rest.contains("freebsd") {
+        "FreeBSD"
+    } else if rest.contains("netbsd") {
+        "NetBSD"
+    } else if rest.contains("openbsd") {
+        "OpenBSD"
+    } else if rest.contains("illumos") {
+        "illumos"
+    } else if rest.contains("solaris") {
+        "Solaris"
+    } else {
+        return name.to_owned();
+    };
+    format!("{arch} {os}")
+}
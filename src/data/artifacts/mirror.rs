@@ -0,0 +1,64 @@
+//! Logic for downloading files from a mirror
+//!
+//! Some projects mirror their release files to a CDN, for people who can't easily reach where
+//! they were published. We rewrite the download urls of files to point at the mirror, along with
+//! any run hints that mention those urls (like `curl ... | sh` for an installer script).
+
+use crate::data::github::GithubRepo;
+
+use super::*;
+
+impl ReleaseArtifacts {
+    /// Point the download urls of files at the user's mirror, if they have one
+    ///
+    /// This should run after everything that adds files or run hints.
+    /// Files that are hosted with the site (from a local directory) are left alone.
+    pub fn apply_mirror(&mut self, config: &ArtifactsConfig, tag: &str, repo: Option<&GithubRepo>) {
+        let Some(mirror) = &config.mirror else {
+            return;
+        };
+        let needs_repo = mirror.url.contains("{owner}") || mirror.url.contains("{repo}");
+        let repo = match repo {
+            Some(repo) => Some(repo),
+            None if needs_repo => {
+                let msg = "Your mirror url uses {owner} or {repo}, but you haven't set your project's repository, so we're not using the mirror.";
+                Message::new(MessageType::Warning, msg).print();
+                return;
+            }
+            None => None,
+        };
+
+        let mut rewrites = vec![];
+        for file_idx in self.file_indices() {
+            if self.local_files.contains_key(&file_idx) {
+                continue;
+            }
+            let mut url = mirror.url.replace("{tag}", tag);
+            if let Some(repo) = repo {
+                url = url
+                    .replace("{owner}", &repo.owner)
+                    .replace("{repo}", &repo.name);
+            }
+            let url = url.replace("{name}", &self.file(file_idx).name);
+            let origin_url = std::mem::replace(&mut self.file_mut(file_idx).download_url, url);
+            rewrites.push((origin_url.clone(), self.file(file_idx).download_url.clone()));
+            self.origin_urls.insert(file_idx, origin_url);
+        }
+
+        // Rewrite longer urls first, in case one is a prefix of another
+        // (e.g. `my-app.tar.gz` and `my-app.tar.gz.sha256`)
+        rewrites.sort_by(|(a, _), (b, _)| b.len().cmp(&a.len()));
+        for installer in &mut self.installers {
+            if let InstallMethod::Run { run_hint, .. } = &mut installer.method {
+                for (origin_url, mirror_url) in &rewrites {
+                    *run_hint = run_hint.replace(origin_url, mirror_url);
+                }
+            }
+        }
+    }
+
+    /// Get where a file was originally published, if we're downloading it from a mirror instead
+    pub fn origin_url(&self, idx: FileIdx) -> Option<&str> {
+        self.origin_urls.get(&idx).map(|url| url.as_str())
+    }
+}
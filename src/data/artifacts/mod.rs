@@ -5,27 +5,49 @@
 //! Data is added to this type with:
 //!
 //! * [`ReleaseArtifacts::add_github`][] (in different file)
+//! * [`ReleaseArtifacts::add_local_dir`][] (in a different file)
 //! * [`ReleaseArtifacts::add_cargo_dist`][] (in a different file)
 //! * [`ReleaseArtifacts::add_inference`][] (in a different file)
 //! * [`ReleaseArtifacts::add_package_managers`][]
+//! * [`ReleaseArtifacts::add_auto_package_managers`][] (in a different file)
+//! * [`ReleaseArtifacts::add_package_manifests`][] (in a different file)
+//!
+//! The user's run hint templates and overrides for specific files are then applied with
+//! [`ReleaseArtifacts::apply_run_hints`][] (in a different file) and [`ReleaseArtifacts::apply_file_config`][],
+//! and files are pointed at the user's mirror with [`ReleaseArtifacts::apply_mirror`][] (in a different file).
 //!
 //! One you've added all the data you want, call [`ReleaseArtifacts::select_installers`][]
 //! to compute the final result, which is stored in [`ReleaseArtifacts::installers_by_target`][].
+//!
+//! Extra details about files that require fetching them (like [`ReleaseArtifacts::fetch_checksums`][])
+//! are only filled in for releases we actually show downloads for.
 
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 
-use axoasset::{LocalAsset, RemoteAsset};
+use axoasset::LocalAsset;
 use camino::Utf8PathBuf;
+use chrono::DateTime;
+use globset::Glob;
 use indexmap::IndexMap;
 use serde::{Serialize, Serializer};
 
-use crate::config::ArtifactsConfig;
 use crate::config::Config;
+use crate::config::{ArtifactPreference, ArtifactsConfig};
+use crate::data::cache::HttpCache;
 use crate::errors::*;
+use crate::message::{Message, MessageType};
 
 use inference::{KNOWN_SCRIPT_EXTS, KNOWN_TARGET_TRIPLES};
 
+mod auto_package_managers;
+mod checksums;
+mod companions;
 pub mod inference;
+mod mirror;
+mod package_manifests;
+pub mod run_hints;
+
+pub use companions::{Companion, CompanionKind};
 
 /// A Target Triple like x86_64-pc-windows-msvc
 pub type TargetTriple = String;
@@ -51,6 +73,21 @@ pub struct ReleaseArtifacts {
     /// What installers to use for each target, in descending order
     /// (so recommend the first one, potentially show the others in tabs)
     targets: BTreeMap<TargetTriple, Vec<InstallerIdx>>,
+    /// Files that came from a local directory (instead of being hosted somewhere)
+    #[serde(skip)]
+    local_files: HashMap<FileIdx, LocalFile>,
+    /// Where files we download from a mirror were originally published
+    #[serde(skip)]
+    origin_urls: HashMap<FileIdx, String>,
+}
+
+/// A File that's on the local filesystem, and gets hosted with the site
+#[derive(Debug, Clone)]
+pub struct LocalFile {
+    /// Where the file is now
+    pub path: Utf8PathBuf,
+    /// Where the file goes in the site (relative to the dist dir)
+    pub site_path: Utf8PathBuf,
 }
 
 /// A handle to a File (equivalent to a pointer into `ReleaseArtifacts::files`)
@@ -68,6 +105,16 @@ pub struct File {
     pub view_path: Option<String>,
     /// A file containing checksums for this one
     pub checksum_file: Option<FileIdx>,
+    /// The SHA-256 of this file (hex-encoded), if we've fetched it
+    pub sha256: Option<String>,
+    /// Signatures, SBOMs and attestations for this file
+    pub companion_files: Vec<Companion>,
+    /// The size of the file in bytes
+    pub size: Option<u64>,
+    /// When the file was last updated (RFC 3339)
+    pub updated_at: Option<String>,
+    /// How many times the file has been downloaded
+    pub download_count: Option<u64>,
     /// Whether artifact_inference should process this file
     ///
     /// Starts true, but can be set to false by other steps to avoid suggesting an installer twice
@@ -75,6 +122,38 @@ pub struct File {
     pub infer: bool,
 }
 
+impl File {
+    /// Get a human-readable version of the size (e.g. "4.2 MiB")
+    pub fn formatted_size(&self) -> Option<String> {
+        const UNITS: &[&str] = &["KiB", "MiB", "GiB", "TiB"];
+        let bytes = self.size?;
+        if bytes < 1024 {
+            return Some(format!("{bytes} B"));
+        }
+        let mut size = bytes as f64 / 1024.0;
+        let mut unit = UNITS[0];
+        for next_unit in &UNITS[1..] {
+            if size < 1024.0 {
+                break;
+            }
+            size /= 1024.0;
+            unit = next_unit;
+        }
+        Some(format!("{size:.1} {unit}"))
+    }
+
+    /// Get a human-readable version of the date the file was last updated
+    pub fn formatted_date(&self) -> Option<String> {
+        self.updated_at.as_ref().map(|date| {
+            if let Ok(parsed_date) = DateTime::parse_from_rfc3339(date) {
+                parsed_date.format("%b %e %Y").to_string()
+            } else {
+                date.to_owned()
+            }
+        })
+    }
+}
+
 /// A handle to an Installer (equivalent to a pointer into [`ReleaseArtifacts::installers`][])
 #[derive(Debug, Copy, Clone, PartialEq, PartialOrd, Eq, Ord, Serialize)]
 pub struct InstallerIdx(pub usize);
@@ -151,6 +230,18 @@ impl ReleaseArtifacts {
         }
     }
 
+    /// Get the app name to filter inferred files by, if any
+    ///
+    /// Releases of a single app don't necessarily name their files after it, so if none
+    /// of the files we'd infer things about mention the app, we don't filter anything out.
+    pub(crate) fn app_filter(&self) -> Option<String> {
+        let app_name = self.app_name.as_ref()?;
+        self.files
+            .values()
+            .any(|file| file.infer && file.name.contains(app_name.as_str()))
+            .then(|| app_name.clone())
+    }
+
     /// Add a file to the list
     pub fn add_file(&mut self, file: File) -> FileIdx {
         let idx = FileIdx(self.files.len());
@@ -163,6 +254,24 @@ impl ReleaseArtifacts {
         idx
     }
 
+    /// Add a file that's on the local filesystem to the list
+    ///
+    /// Anything we need to read from the file will be read from its path instead of downloaded.
+    pub fn add_local_file(&mut self, file: File, local_file: LocalFile) -> FileIdx {
+        let idx = self.add_file(file);
+        self.local_files.insert(idx, local_file);
+        idx
+    }
+
+    /// Get the contents of a file, from the local filesystem if we have it, or else its download url
+    pub(crate) async fn fetch_file_text(&self, idx: FileIdx, cache: &HttpCache) -> Result<String> {
+        if let Some(local_file) = self.local_files.get(&idx) {
+            Ok(LocalAsset::load_string(local_file.path.as_str())?)
+        } else {
+            cache.fetch_text(&self.file(idx).download_url).await
+        }
+    }
+
     /// Add an installer to the list
     pub fn add_installer(&mut self, installer: Installer) -> InstallerIdx {
         let idx = InstallerIdx(self.installers.len());
@@ -190,6 +299,10 @@ impl ReleaseArtifacts {
     pub fn files(&self) -> impl Iterator<Item = &File> {
         self.files.values()
     }
+    /// Get all the files that need to be copied into the site
+    pub fn local_files(&self) -> impl Iterator<Item = &LocalFile> {
+        self.local_files.values()
+    }
     /// Get an installer
     pub fn installer(&self, idx: InstallerIdx) -> &Installer {
         &self.installers[idx.0]
@@ -232,6 +345,93 @@ impl ReleaseArtifacts {
         }
     }
 
+    /// Apply the user's overrides for files matching the globs in `artifacts.files`
+    ///
+    /// This should run after all the other data sources, so it can correct them. Files that
+    /// nothing else turned into an installer get one (a download) if they match a glob that
+    /// says which targets they're for.
+    pub fn apply_file_config(&mut self, config: &ArtifactsConfig) -> Result<()> {
+        for (pattern, file_config) in &config.files {
+            let matcher = Glob::new(pattern)
+                .map_err(|e| OrandaError::InvalidGlob {
+                    pattern: pattern.clone(),
+                    details: e,
+                })?
+                .compile_matcher();
+
+            let targets = file_config.targets.as_ref().map(|targets| {
+                targets
+                    .iter()
+                    .flat_map(inference::targets_for_config)
+                    .collect::<Vec<_>>()
+            });
+            let preference = file_config.preference.map(|preference| match preference {
+                ArtifactPreference::Preferred => InstallerPreference::Preferred,
+                ArtifactPreference::Native => InstallerPreference::Native,
+                ArtifactPreference::Script => InstallerPreference::Script,
+                ArtifactPreference::Custom => InstallerPreference::Custom,
+                ArtifactPreference::Archive => InstallerPreference::Archive,
+            });
+
+            for file_idx in self.file_indices() {
+                let file = self.file(file_idx);
+                if !matcher.is_match(&file.name) {
+                    continue;
+                }
+
+                // Find the installers for this file, making one if there aren't any
+                let mut installers: Vec<usize> = self
+                    .installers
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, installer)| match installer.method {
+                        InstallMethod::Download { file } => file == file_idx,
+                        InstallMethod::Run { file, .. } => file == Some(file_idx),
+                    })
+                    .map(|(idx, _)| idx)
+                    .collect();
+                // Without targets, we'd have no idea which platforms to offer it on
+                let has_targets = targets.as_ref().is_some_and(|targets| !targets.is_empty());
+                if installers.is_empty() && has_targets {
+                    let label = Utf8PathBuf::from(&file.name)
+                        .extension()
+                        .unwrap_or("download")
+                        .to_owned();
+                    let installer = Installer {
+                        label,
+                        description: String::new(),
+                        targets: HashMap::new(),
+                        method: InstallMethod::Download { file: file_idx },
+                        display: DisplayPreference::Preferred,
+                    };
+                    installers.push(self.add_installer(installer).0);
+                }
+
+                for idx in installers {
+                    let installer = &mut self.installers[idx];
+                    if let Some(label) = &file_config.label {
+                        installer.label = label.clone();
+                    }
+                    if let Some(description) = &file_config.description {
+                        installer.description = description.clone();
+                    }
+                    // Keep the old preference if we're only changing the targets
+                    let preference = preference
+                        .or_else(|| installer.targets.values().min().copied())
+                        .unwrap_or(InstallerPreference::Custom);
+                    let targets = targets
+                        .clone()
+                        .unwrap_or_else(|| installer.targets.keys().cloned().collect());
+                    // An empty list would mean every target, which nobody asked for
+                    if !targets.is_empty() {
+                        installer.targets = preference_to_targets(targets, preference);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Now that we've added all the data sources, select installers for each target
     pub fn select_installers(&mut self, artifacts_config: &ArtifactsConfig) {
         // Hide anything that the user has asked for
@@ -240,7 +440,36 @@ impl ReleaseArtifacts {
                 installer.display = DisplayPreference::Hidden;
             }
         }
-        for target in KNOWN_TARGET_TRIPLES.iter().copied().flatten().copied() {
+        // Consider every target we know about, and any others that installers claim to support
+        let mut all_targets: BTreeSet<&Targ> = KNOWN_TARGET_TRIPLES
+            .iter()
+            .copied()
+            .flatten()
+            .copied()
+            .collect();
+        let mentioned_targets: Vec<TargetTriple> = self
+            .installers
+            .iter()
+            .flat_map(|installer| installer.targets.keys().cloned())
+            .collect();
+        all_targets.extend(mentioned_targets.iter().map(|t| t.as_str()));
+
+        // Gather up the explicit priorities the user gave installers on each target
+        let mut priorities: HashMap<(&str, TargetTriple), i64> = HashMap::new();
+        for (label, installer_config) in &artifacts_config.installers {
+            for (target, priority) in installer_config.priority.iter().flatten() {
+                for triple in inference::targets_for_config(target) {
+                    priorities.insert((label.as_str(), triple), *priority);
+                }
+            }
+        }
+        let priority = |installer: &Installer, target: &Targ| {
+            priorities
+                .get(&(installer.label.as_str(), target.to_owned()))
+                .copied()
+        };
+
+        for target in all_targets {
             // Gather up all the installers into an array
             let mut installers = vec![];
             for (idx, installer) in self.installers() {
@@ -253,13 +482,16 @@ impl ReleaseArtifacts {
                 }
             }
 
-            // Sort the array
+            // Sort the array (explicit priorities first, then by preference)
             installers.sort_by(|(idx_a, pref_a), (idx_b, pref_b)| {
                 let installer_a = self.installer(*idx_a);
                 let installer_b = self.installer(*idx_b);
+                let priority_a = priority(installer_a, target);
+                let priority_b = priority(installer_b, target);
 
-                pref_a
-                    .cmp(pref_b)
+                (priority_a.is_none(), priority_a)
+                    .cmp(&(priority_b.is_none(), priority_b))
+                    .then_with(|| pref_a.cmp(pref_b))
                     .then_with(|| installer_a.label.cmp(&installer_b.label))
             });
 
@@ -269,18 +501,54 @@ impl ReleaseArtifacts {
                 self.targets.insert(target.to_owned(), installers);
             }
         }
+
+        // Finally apply any new labels/descriptions (we're done looking things up by label)
+        for installer in &mut self.installers {
+            let Some(installer_config) = artifacts_config.installers.get(&installer.label) else {
+                continue;
+            };
+            if let Some(description) = &installer_config.description {
+                installer.description = description.clone();
+            }
+            if let Some(label) = &installer_config.label {
+                installer.label = label.clone();
+            }
+        }
     }
 
     /// Make shell scripts viewable by copying the files to be statically hosted instead of hotlinked
     pub fn make_scripts_viewable(&mut self, config: &Config) -> Result<()> {
-        for file in self.files.values_mut() {
+        for file_idx in self.file_indices() {
+            let file = self.file(file_idx);
             if KNOWN_SCRIPT_EXTS.iter().any(|ext| file.name.ends_with(ext)) {
-                let path = write_source(config, file)?;
-                file.view_path = Some(path);
+                match self.write_source(config, file_idx) {
+                    Ok(path) => self.file_mut(file_idx).view_path = Some(path),
+                    Err(OrandaError::OfflineCacheMiss { .. }) => {
+                        let msg = format!(
+                            "We're offline and don't have {} cached, so it won't be viewable on your site.",
+                            file.name
+                        );
+                        Message::new(MessageType::Warning, &msg).print();
+                    }
+                    Err(e) => return Err(e),
+                }
             }
         }
         Ok(())
     }
+
+    /// Write a copy of a file into the site, so people can read it
+    fn write_source(&self, config: &Config, file_idx: FileIdx) -> Result<String> {
+        let file_path = format!("{}.txt", &self.file(file_idx).name);
+        let full_file_path = Utf8PathBuf::from(&config.build.dist_dir).join(&file_path);
+        if !full_file_path.exists() {
+            let cache = HttpCache::new(config.build.cache_mode);
+            let file_string_future = self.fetch_file_text(file_idx, &cache);
+            let file_string = tokio::runtime::Handle::current().block_on(file_string_future)?;
+            LocalAsset::write_new(&file_string, &full_file_path)?;
+        }
+        Ok(file_path)
+    }
 }
 
 /// Take an installer preference and uniformly apply it to every given TargetTriple
@@ -315,17 +583,6 @@ where
 }
 
 /// Make the source of a file available on the server
-fn write_source(config: &Config, file: &File) -> Result<String> {
-    let file_path = format!("{}.txt", &file.name);
-    let full_file_path = Utf8PathBuf::from(&config.build.dist_dir).join(&file_path);
-    if !full_file_path.exists() {
-        let file_string_future = RemoteAsset::load_string(&file.download_url);
-        let file_string = tokio::runtime::Handle::current().block_on(file_string_future)?;
-        LocalAsset::write_new(&file_string, &full_file_path)?;
-    }
-    Ok(file_path)
-}
-
 fn simple_run_installer(label: &str, script: &str) -> Installer {
     let run_hint = script.to_owned();
     Installer {
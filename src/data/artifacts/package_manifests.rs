@@ -0,0 +1,135 @@
+//! Logic for turning package manager manifests in a release into installers
+//!
+//! Releases made with tools like goreleaser often include a Homebrew formula (`my-app.rb`),
+//! a Scoop manifest (`my-app.json`) and winget manifests (`Publisher.MyApp.installer.yaml`).
+//! Nobody wants to download these, so we hide them and suggest the package manager instead.
+
+use crate::config::ArtifactSystem;
+use crate::data::github::GithubRepo;
+
+use super::inference::targets_for_system;
+use super::*;
+
+const EXT_HOMEBREW_FORMULA: &str = ".rb";
+const EXT_SCOOP_MANIFEST: &str = ".json";
+const EXTS_WINGET_MANIFEST: &[&str] = &[".yaml", ".yml"];
+
+impl ReleaseArtifacts {
+    /// Find package manager manifests and suggest installing with those package managers
+    ///
+    /// The manifests themselves are excluded from any further inference.
+    pub fn add_package_manifests(&mut self, config: &ArtifactsConfig, repo: Option<&GithubRepo>) {
+        // Homebrew formulae need to be in a tap, so we can only suggest them if we know it
+        let tap = config
+            .homebrew_tap
+            .clone()
+            .or_else(|| repo.map(|_| "{owner}/tap".to_owned()))
+            .map(|tap| match repo {
+                Some(repo) => tap.replace("{owner}", &repo.owner),
+                None => tap,
+            });
+
+        // Releases are full of other JSON files, so only ones named after the app are Scoop's
+        let scoop_names: Vec<String> = self
+            .app_name
+            .iter()
+            .cloned()
+            .chain(repo.map(|repo| repo.name.clone()))
+            .collect();
+
+        let app_name = self.app_filter();
+        let mut winget_ids = vec![];
+        for file_idx in self.file_indices() {
+            let file = self.file(file_idx);
+            if !file.infer {
+                continue;
+            }
+            if let Some(app_name) = &app_name {
+                if !file.name.contains(app_name) {
+                    continue;
+                }
+            }
+
+            if let Some(formula) = file.name.strip_suffix(EXT_HOMEBREW_FORMULA) {
+                let formula = formula.to_owned();
+                let run_hint = tap
+                    .as_ref()
+                    .map(|tap| format!("brew install {tap}/{formula}"));
+                self.file_mut(file_idx).infer = false;
+                if let Some(run_hint) = run_hint {
+                    let targets = targets_for_system(ArtifactSystem::Mac);
+                    self.add_manifest_installer("homebrew", targets, run_hint);
+                } else {
+                    tracing::debug!(
+                        "Not suggesting Homebrew for {formula}, because we don't know what tap it's in"
+                    );
+                }
+            } else if is_scoop_manifest(&file.name, &scoop_names) {
+                let run_hint = format!("scoop install {}", file.download_url);
+                self.file_mut(file_idx).infer = false;
+                self.add_manifest_installer(
+                    "scoop",
+                    targets_for_system(ArtifactSystem::Windows),
+                    run_hint,
+                );
+            } else if let Some(id) = winget_id(&file.name) {
+                self.file_mut(file_idx).infer = false;
+                // A package has several manifests, but we only want to suggest it once
+                if !winget_ids.contains(&id) {
+                    winget_ids.push(id);
+                }
+            }
+        }
+
+        for id in winget_ids {
+            let run_hint = format!("winget install --id {id} --exact");
+            self.add_manifest_installer(
+                "winget",
+                targets_for_system(ArtifactSystem::Windows),
+                run_hint,
+            );
+        }
+    }
+
+    fn add_manifest_installer(
+        &mut self,
+        label: &str,
+        targets: Vec<TargetTriple>,
+        run_hint: String,
+    ) {
+        self.add_installer(Installer {
+            label: label.to_owned(),
+            description: String::new(),
+            targets: preference_to_targets(targets, InstallerPreference::Native),
+            method: InstallMethod::Run {
+                file: None,
+                run_hint,
+            },
+            display: DisplayPreference::Preferred,
+        });
+    }
+}
+
+/// Whether this looks like the Scoop manifest of one of the given apps (`my-app.json`)
+fn is_scoop_manifest(name: &str, app_names: &[String]) -> bool {
+    name.strip_suffix(EXT_SCOOP_MANIFEST).map_or(false, |stem| {
+        app_names.iter().any(|app_name| app_name == stem)
+    })
+}
+
+/// Get the package identifier of a winget manifest (`Publisher.MyApp.installer.yaml`)
+fn winget_id(name: &str) -> Option<String> {
+    let stem = EXTS_WINGET_MANIFEST
+        .iter()
+        .find_map(|ext| name.strip_suffix(ext))?;
+    let id = if let Some(id) = stem.strip_suffix(".installer") {
+        id
+    } else if let Some((id, _locale)) = stem.split_once(".locale.") {
+        id
+    } else {
+        stem
+    };
+    // Identifiers are always `Publisher.Package`
+    let (publisher, package) = id.split_once('.')?;
+    (!publisher.is_empty() && !package.is_empty()).then(|| id.to_owned())
+}
@@ -0,0 +1,103 @@
+//! Logic for telling people how to run installer scripts
+//!
+//! Run hints are templates per kind of script, where `{url}` is replaced with where to
+//! download the script and `{file}` with its name.
+
+use super::*;
+use crate::config::ScriptRunHintsConfig;
+
+/// The default way to run a shell script
+pub const DEFAULT_SHELL_RUN_HINT: &str = "curl --proto '=https' --tlsv1.2 -LsSf {url} | sh";
+/// The default way to run a PowerShell script
+pub const DEFAULT_POWERSHELL_RUN_HINT: &str = "irm {url} | iex";
+
+/// The kinds of scripts we know how to run
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ScriptKind {
+    /// A `.sh` script
+    Shell,
+    /// A `.ps1` script
+    Powershell,
+}
+
+impl ScriptKind {
+    /// Get the kind of script a file is, if it's a script
+    pub fn for_file(file: &File) -> Option<Self> {
+        if file.name.ends_with(".sh") {
+            Some(ScriptKind::Shell)
+        } else if file.name.ends_with(".ps1") {
+            Some(ScriptKind::Powershell)
+        } else {
+            None
+        }
+    }
+
+    /// The template we use for this kind of script if the user doesn't give us one
+    pub fn default_template(self) -> &'static str {
+        match self {
+            ScriptKind::Shell => DEFAULT_SHELL_RUN_HINT,
+            ScriptKind::Powershell => DEFAULT_POWERSHELL_RUN_HINT,
+        }
+    }
+
+    fn config(self, config: &ArtifactsConfig) -> &ScriptRunHintsConfig {
+        match self {
+            ScriptKind::Shell => &config.run_hints.shell,
+            ScriptKind::Powershell => &config.run_hints.powershell,
+        }
+    }
+}
+
+/// Fill in a run hint template for a file
+pub fn render_run_hint(template: &str, file: &File) -> String {
+    template
+        .replace("{url}", &file.download_url)
+        .replace("{file}", &file.name)
+}
+
+impl ReleaseArtifacts {
+    /// Apply the user's run hint templates to installer scripts, and add their extra variants
+    ///
+    /// This applies to scripts from every source (including cargo-dist), so it should run
+    /// after all of them.
+    pub fn apply_run_hints(&mut self, config: &ArtifactsConfig) {
+        let mut variants = vec![];
+        for installer in &mut self.installers {
+            let InstallMethod::Run {
+                file: Some(file_idx),
+                run_hint,
+            } = &mut installer.method
+            else {
+                continue;
+            };
+            let file = self
+                .files
+                .get_index(file_idx.0)
+                .expect("installer referred to a file that doesn't exist")
+                .1;
+            let Some(kind) = ScriptKind::for_file(file) else {
+                continue;
+            };
+            let kind_config = kind.config(config);
+
+            if let Some(template) = &kind_config.template {
+                *run_hint = render_run_hint(template, file);
+            }
+            for (label, template) in &kind_config.variants {
+                variants.push(Installer {
+                    label: label.clone(),
+                    description: String::new(),
+                    targets: installer.targets.clone(),
+                    method: InstallMethod::Run {
+                        file: Some(*file_idx),
+                        run_hint: render_run_hint(template, file),
+                    },
+                    display: installer.display,
+                });
+            }
+        }
+        for installer in variants {
+            self.add_installer(installer);
+        }
+    }
+}
@@ -0,0 +1,226 @@
+//! An on-disk cache for the things we fetch over HTTP
+//!
+//! Every build fetches releases, dist-manifests, oranda's CSS and installer scripts.
+//! Most of these never change once published, so we store responses along with their
+//! `ETag`/`Last-Modified` headers and use conditional requests to revalidate them.
+//!
+//! The cache lives in the platform's cache dir (e.g. `$XDG_CACHE_HOME/oranda`), and can
+//! be moved by setting the `ORANDA_CACHE_DIR` environment variable.
+
+use std::env;
+
+use axoasset::LocalAsset;
+use camino::Utf8PathBuf;
+use octolotl::Requestable;
+use reqwest::header::{ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED, USER_AGENT};
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::errors::*;
+
+/// Environment variable that overrides where the cache is stored
+pub const CACHE_DIR_ENV: &str = "ORANDA_CACHE_DIR";
+
+/// How the cache should be used
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum CacheMode {
+    /// Use cached responses, but check with the server that they're still up to date
+    #[default]
+    Revalidate,
+    /// Ignore cached responses and fetch everything again (still updating the cache)
+    Refresh,
+    /// Use cached responses without checking with the server, only fetching what's missing
+    Reuse,
+    /// Never touch the network, only use cached responses
+    Offline,
+}
+
+impl CacheMode {
+    /// Whether we're not allowed to touch the network at all
+    pub fn is_offline(self) -> bool {
+        self == CacheMode::Offline
+    }
+}
+
+/// A cached response
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheEntry {
+    /// The URL this response is for
+    url: String,
+    /// The `ETag` header of the response
+    etag: Option<String>,
+    /// The `Last-Modified` header of the response
+    last_modified: Option<String>,
+    /// The body of the response
+    body: String,
+}
+
+/// A handle to the on-disk HTTP cache
+#[derive(Debug, Clone)]
+pub struct HttpCache {
+    /// Where entries are stored (None if we couldn't find anywhere to put them)
+    dir: Option<Utf8PathBuf>,
+    /// How entries should be used
+    mode: CacheMode,
+}
+
+impl HttpCache {
+    /// Get a handle to the cache in its default location
+    pub fn new(mode: CacheMode) -> Self {
+        Self {
+            dir: cache_dir(),
+            mode,
+        }
+    }
+
+    /// Get a handle to a cache stored in a specific directory
+    pub fn with_dir(dir: Utf8PathBuf, mode: CacheMode) -> Self {
+        Self {
+            dir: Some(dir),
+            mode,
+        }
+    }
+
+    /// Fetch something from Github, via the octolotl proxy if possible
+    pub async fn fetch_github(&self, item: &impl Requestable) -> Result<String> {
+        // We key everything on the "real" Github URL so that the proxy and Github share entries
+        let key = item.github_url();
+        if self.mode.is_offline() {
+            return self.fetch_with_key(&key, &key).await;
+        }
+        match self.fetch_with_key(&key, &item.proxy_url()).await {
+            Ok(body) => Ok(body),
+            Err(e) => {
+                tracing::warn!(
+                    "Fetching from proxy encountered an error: {e}. Falling back to GitHub directly..."
+                );
+                self.fetch_with_key(&key, &key).await
+            }
+        }
+    }
+
+    /// Fetch the text at this URL
+    pub async fn fetch_text(&self, url: &str) -> Result<String> {
+        self.fetch_with_key(url, url).await
+    }
+
+    async fn fetch_with_key(&self, key: &str, url: &str) -> Result<String> {
+        let path = self.entry_path(key);
+        let cached = match (&path, self.mode) {
+            (Some(path), CacheMode::Revalidate | CacheMode::Reuse | CacheMode::Offline) => {
+                read_entry(path)
+            }
+            _ => None,
+        };
+
+        match (&cached, self.mode) {
+            (Some(entry), CacheMode::Reuse | CacheMode::Offline) => {
+                tracing::debug!("Reusing cached response for {url}");
+                return Ok(entry.body.clone());
+            }
+            (None, CacheMode::Offline) => {
+                return Err(OrandaError::OfflineCacheMiss {
+                    url: url.to_owned(),
+                });
+            }
+            _ => {}
+        }
+
+        let client = reqwest::Client::new();
+        let mut request = client.get(url).header(USER_AGENT, user_agent());
+        if let Some(entry) = &cached {
+            if let Some(etag) = &entry.etag {
+                request = request.header(IF_NONE_MATCH, etag);
+            }
+            if let Some(last_modified) = &entry.last_modified {
+                request = request.header(IF_MODIFIED_SINCE, last_modified);
+            }
+        }
+        let response = request.send().await?;
+
+        if response.status() == StatusCode::NOT_MODIFIED {
+            if let Some(entry) = cached {
+                tracing::debug!("Cached response for {url} is still up to date");
+                return Ok(entry.body);
+            }
+        }
+
+        let response = response.error_for_status()?;
+        let header = |name| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(|v| v.to_owned())
+        };
+        let etag = header(ETAG);
+        let last_modified = header(LAST_MODIFIED);
+        let body = response.text().await?;
+
+        if let Some(path) = path {
+            let entry = CacheEntry {
+                url: url.to_owned(),
+                etag,
+                last_modified,
+                body,
+            };
+            write_entry(&path, &entry);
+            Ok(entry.body)
+        } else {
+            Ok(body)
+        }
+    }
+
+    fn entry_path(&self, key: &str) -> Option<Utf8PathBuf> {
+        let dir = self.dir.as_ref()?;
+        let hash = Sha256::digest(key.as_bytes());
+        let name: String = hash.iter().map(|b| format!("{b:02x}")).collect();
+        Some(dir.join(format!("{name}.json")))
+    }
+}
+
+/// Find the directory the cache should live in
+fn cache_dir() -> Option<Utf8PathBuf> {
+    if let Ok(dir) = env::var(CACHE_DIR_ENV) {
+        return Some(Utf8PathBuf::from(dir));
+    }
+
+    let base = if cfg!(windows) {
+        env::var("LOCALAPPDATA").ok().map(Utf8PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        env::var("HOME")
+            .ok()
+            .map(|home| Utf8PathBuf::from(home).join("Library").join("Caches"))
+    } else {
+        env::var("XDG_CACHE_HOME")
+            .ok()
+            .filter(|dir| !dir.is_empty())
+            .map(Utf8PathBuf::from)
+            .or_else(|| {
+                env::var("HOME")
+                    .ok()
+                    .map(|home| Utf8PathBuf::from(home).join(".cache"))
+            })
+    };
+    base.map(|base| base.join("oranda"))
+}
+
+fn read_entry(path: &Utf8PathBuf) -> Option<CacheEntry> {
+    let contents = LocalAsset::load_string(path).ok()?;
+    serde_json::from_str(&contents).ok()
+}
+
+fn write_entry(path: &Utf8PathBuf, entry: &CacheEntry) {
+    // Failing to write to the cache is never fatal, we'll just fetch it again next time
+    let result = serde_json::to_string(entry)
+        .map_err(OrandaError::from)
+        .and_then(|contents| Ok(LocalAsset::write_new_all(&contents, path)?));
+    if let Err(e) = result {
+        tracing::warn!("Failed to write {path} to the cache: {e}");
+    }
+}
+
+fn user_agent() -> String {
+    format!("oranda-{}", env!("CARGO_PKG_VERSION"))
+}
@@ -0,0 +1,165 @@
+//! A lenient reader for cargo-dist's dist-manifest.json
+//!
+//! `cargo_dist_schema` only reads manifests in the shape of the one version it was published
+//! with, so releases built by older or newer versions of cargo-dist would lose their installers.
+//! Instead we read just the parts we use, and skip anything we can't make sense of (an unknown
+//! kind of artifact, or a field that changed shape) rather than the whole manifest.
+
+use std::collections::BTreeMap;
+
+use axoasset::{AxoassetError, SourceFile};
+use serde::Deserialize;
+
+/// The parts of a dist-manifest.json that we use
+#[derive(Debug, Clone)]
+pub struct DistManifest {
+    /// The version of cargo-dist that made this, if it says
+    pub dist_version: Option<String>,
+    /// The (git) tag associated with this announcement
+    pub announcement_tag: Option<String>,
+    /// Whether this announcement appears to be a prerelease
+    pub announcement_is_prerelease: bool,
+    /// A changelog for the announcement
+    pub announcement_changelog: Option<String>,
+    /// App releases we're distributing
+    pub releases: Vec<DistRelease>,
+    /// The artifacts included in this announcement, referenced by releases
+    pub artifacts: BTreeMap<String, DistArtifact>,
+}
+
+/// A release of an app
+#[derive(Debug, Clone, Deserialize)]
+pub struct DistRelease {
+    /// The name of the app
+    pub app_name: String,
+    /// The version of the app
+    #[serde(default)]
+    pub app_version: Option<String>,
+    /// The ids of the artifacts for this release
+    #[serde(default)]
+    pub artifacts: Vec<String>,
+}
+
+/// A distributable artifact that's part of a release (i.e. a zip or installer)
+#[derive(Debug, Clone, Deserialize)]
+pub struct DistArtifact {
+    /// The name of the file, if the artifact is a file at all
+    #[serde(default)]
+    pub name: Option<String>,
+    /// The kind of artifact this is
+    pub kind: ArtifactKind,
+    /// The platforms this artifact is for
+    #[serde(default)]
+    pub target_triples: Vec<String>,
+    /// A string describing how to install this
+    #[serde(default)]
+    pub install_hint: Option<String>,
+    /// A brief description of what this artifact is
+    #[serde(default)]
+    pub description: Option<String>,
+    /// The id of an artifact containing the checksum of this one
+    #[serde(default)]
+    pub checksum: Option<String>,
+    /// Checksums of the file, keyed by algorithm (newer versions of cargo-dist include these)
+    #[serde(default)]
+    pub checksums: BTreeMap<String, String>,
+}
+
+/// The kinds of artifacts we know what to do with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ArtifactKind {
+    /// A zip or a tarball
+    ExecutableZip,
+    /// Standalone symbols/debuginfo for a build
+    Symbols,
+    /// An installer
+    Installer,
+    /// A checksum of another artifact
+    Checksum,
+    /// A checksum of every artifact
+    UnifiedChecksum,
+    /// Anything else (including kinds from newer versions of cargo-dist)
+    #[serde(other)]
+    Unknown,
+}
+
+/// The top level of a manifest, before we look at its releases and artifacts individually
+#[derive(Deserialize)]
+struct RawDistManifest {
+    #[serde(default)]
+    dist_version: Option<String>,
+    #[serde(default)]
+    announcement_tag: Option<String>,
+    #[serde(default)]
+    announcement_is_prerelease: bool,
+    #[serde(default)]
+    announcement_changelog: Option<String>,
+    #[serde(default)]
+    releases: Vec<serde_json::Value>,
+    #[serde(default)]
+    artifacts: BTreeMap<String, serde_json::Value>,
+}
+
+/// The result of reading a manifest
+pub enum ManifestRead {
+    /// We could read it, except for the listed releases/artifacts
+    Read {
+        manifest: DistManifest,
+        skipped: Vec<String>,
+    },
+    /// It's not a manifest at all
+    Malformed(AxoassetError),
+}
+
+impl DistManifest {
+    /// Read a dist-manifest.json made by any version of cargo-dist
+    ///
+    /// This includes ones from before cargo-dist had a stable format, since the parts we use
+    /// were there from the start.
+    pub fn read(src: &SourceFile) -> ManifestRead {
+        let raw = match src.deserialize_json::<RawDistManifest>() {
+            Ok(raw) => raw,
+            Err(e) => return ManifestRead::Malformed(e),
+        };
+
+        let mut skipped = vec![];
+        let mut releases = vec![];
+        for (idx, release) in raw.releases.into_iter().enumerate() {
+            match serde_json::from_value::<DistRelease>(release) {
+                Ok(release) => releases.push(release),
+                Err(e) => skipped.push(format!("release #{} ({e})", idx + 1)),
+            }
+        }
+        let mut artifacts = BTreeMap::new();
+        for (id, artifact) in raw.artifacts {
+            match serde_json::from_value::<DistArtifact>(artifact) {
+                Ok(artifact) => {
+                    artifacts.insert(id, artifact);
+                }
+                Err(e) => skipped.push(format!("{id} ({e})")),
+            }
+        }
+
+        let manifest = DistManifest {
+            dist_version: raw.dist_version,
+            announcement_tag: raw.announcement_tag,
+            announcement_is_prerelease: raw.announcement_is_prerelease,
+            announcement_changelog: raw.announcement_changelog,
+            releases,
+            artifacts,
+        };
+        ManifestRead::Read { manifest, skipped }
+    }
+
+    /// Get the artifacts of a release, along with their ids
+    pub fn artifacts_for_release<'a>(
+        &'a self,
+        release: &'a DistRelease,
+    ) -> impl Iterator<Item = (&'a str, &'a DistArtifact)> {
+        release
+            .artifacts
+            .iter()
+            .filter_map(|id| Some((id.as_str(), self.artifacts.get(id)?)))
+    }
+}
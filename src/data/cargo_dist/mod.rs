@@ -0,0 +1,202 @@
+use camino::Utf8PathBuf;
+
+mod manifest;
+pub use manifest::{ArtifactKind, DistArtifact, DistManifest, DistRelease, ManifestRead};
+
+use crate::data::artifacts::{
+    preference_to_targets, DisplayPreference, File, InstallMethod, Installer, InstallerPreference,
+    ReleaseArtifacts,
+};
+use crate::data::github::GithubRepo;
+use crate::message::{Message, MessageType};
+
+pub const MANIFEST_FILENAME: &str = "dist-manifest.json";
+
+impl ReleaseArtifacts {
+    /// Add placeholder files for the artifacts of a release that hasn't been published yet
+    ///
+    /// Their download urls are where the forge will host them once the release's tag is
+    /// published, so this lets you preview the installers of the upcoming release.
+    /// Files that we already have (e.g. from a local directory) are left alone.
+    pub fn add_unreleased_cargo_dist(
+        &mut self,
+        manifest: &DistManifest,
+        repo: Option<&GithubRepo>,
+        version_tag: &str,
+    ) {
+        let tag = manifest.announcement_tag.as_deref().unwrap_or(version_tag);
+        let names = self
+            .apps_to_show(manifest)
+            .into_iter()
+            .flat_map(|app| manifest.artifacts_for_release(app))
+            .flat_map(|(_, artifact)| [&artifact.name, &artifact.checksum])
+            .flatten();
+        for name in names {
+            if self.file_idx(name).is_some() {
+                continue;
+            }
+            let Some(repo) = repo else {
+                let msg = "Found a dist-manifest.json for an upcoming release, but we don't know where its files will be published. Set your project's repository to preview its downloads.";
+                Message::new(MessageType::Warning, msg).print();
+                return;
+            };
+            self.add_file(File {
+                name: name.clone(),
+                download_url: repo.release_asset_url(tag, name),
+                // We can't know any of this until it's published
+                view_path: None,
+                checksum_file: None,
+                sha256: None,
+                companion_files: vec![],
+                size: None,
+                updated_at: None,
+                download_count: None,
+                infer: true,
+            });
+        }
+    }
+
+    /// Incorporate data from cargo-dist into the ReleaseArtifacts
+    ///
+    /// If the manifest has releases of several apps, only the one we're focusing on is used.
+    pub fn add_cargo_dist(&mut self, manifest: &DistManifest, version_tag: &str) {
+        // NOTE: this code currently assumes `self.files` has already been populated
+        // by e.g. calling `add_github` or whatever future system for discovering artifacts.
+        // If the manifest refers to files that don't exist, they will be skipped.
+
+        let apps = self.apps_to_show(manifest);
+        let other_apps: Vec<&str> = manifest
+            .releases
+            .iter()
+            .filter(|app| !apps.iter().any(|shown| shown.app_name == app.app_name))
+            .map(|app| app.app_name.as_str())
+            .collect();
+        if !other_apps.is_empty() {
+            let msg = format!(
+                "The dist-manifest.json for {version_tag} also has releases of {}, which won't be shown. Set artifacts.app_name if this site is about one of those instead.",
+                other_apps.join(", ")
+            );
+            Message::new(MessageType::Warning, &msg).print();
+        } else if let (true, Some(app_name)) = (apps.len() > 1, &self.app_name) {
+            let names: Vec<&str> = apps.iter().map(|app| app.app_name.as_str()).collect();
+            let msg = format!(
+                "The dist-manifest.json for {version_tag} has releases of several apps ({}), and none of them are named {app_name}, so all of them will be shown. Set artifacts.app_name to pick one.",
+                names.join(", "),
+            );
+            Message::new(MessageType::Warning, &msg).print();
+        }
+        // Now we know which app's files to infer things about
+        self.app_name = match apps.as_slice() {
+            [app] => Some(app.app_name.clone()),
+            _ => None,
+        };
+
+        for app in apps {
+            for (id, artifact) in manifest.artifacts_for_release(app) {
+                let label;
+                let method;
+                let preference;
+                let file = artifact.name.as_ref().and_then(|n| self.file_idx(n));
+
+                // If this artifact has a checksum, register it
+                let checksum_file = artifact.checksum.as_ref().and_then(|n| self.file_idx(n));
+                if let Some(file) = file {
+                    let file = self.file_mut(file);
+                    file.checksum_file = checksum_file;
+                    // Newer manifests just tell us the checksum
+                    if let Some(sha256) = artifact.checksums.get("sha256") {
+                        file.sha256 = Some(sha256.clone());
+                    }
+                }
+
+                match artifact.kind {
+                    ArtifactKind::ExecutableZip => {
+                        // Skip this if the file is somehow missing
+                        let Some(file) = file else {
+                            continue;
+                        };
+                        label = if id.ends_with(".zip") {
+                            "zip".to_owned()
+                        } else {
+                            "tarball".to_owned()
+                        };
+                        method = InstallMethod::Download { file };
+                        preference = InstallerPreference::Archive;
+                    }
+                    ArtifactKind::Installer => {
+                        if let Some(install_hint) = &artifact.install_hint {
+                            // If there's an install-hint, assume this is something we're telling them to run
+                            //
+                            // Special hack: demote npm-packages, which cargo-dist presents kind of weird
+                            let file = if id.contains("npm-package") {
+                                preference = InstallerPreference::Custom;
+                                None
+                            } else {
+                                preference = InstallerPreference::Script;
+                                file
+                            };
+
+                            method = InstallMethod::Run {
+                                file,
+                                run_hint: install_hint.clone(),
+                            };
+                        } else if let Some(file) = file {
+                            // If there's no install-hint, but there is a proper file name, just suggest downloading it
+                            // while assuming this is some kind of custom installer
+                            method = InstallMethod::Download { file };
+                            preference = InstallerPreference::Custom;
+                        } else {
+                            // Must be some new cargo-dist thing we don't understand, move along
+                            continue;
+                        };
+                        label = if id.ends_with(".sh") {
+                            "shell".to_owned()
+                        } else if id.ends_with(".ps1") {
+                            "powershell".to_owned()
+                        } else if id.contains("npm-package") {
+                            "npm".to_owned()
+                        } else {
+                            Utf8PathBuf::from(id).extension().unwrap_or(id).to_owned()
+                        };
+                    }
+                    _ => {
+                        // We don't care about these *yet*
+                        // (notably skipped: Symbols, Checksum)
+                        continue;
+                    }
+                };
+                let targets = preference_to_targets(artifact.target_triples.clone(), preference);
+                let installer = Installer {
+                    label,
+                    description: artifact.description.clone().unwrap_or_default(),
+                    targets,
+                    method,
+                    display: DisplayPreference::Preferred,
+                };
+                self.add_installer(installer);
+
+                // If this is a proper file, disable inference of this file's properties
+                if let Some(file) = file {
+                    self.file_mut(file).infer = false;
+                }
+            }
+        }
+    }
+
+    /// Get the releases in a manifest of the app we're focusing on
+    ///
+    /// If the manifest doesn't have that app (e.g. because we're using the project's name,
+    /// and the app is named something else), we'd rather show every app than nothing.
+    fn apps_to_show<'a>(&self, manifest: &'a DistManifest) -> Vec<&'a DistRelease> {
+        let focused: Vec<&DistRelease> = manifest
+            .releases
+            .iter()
+            .filter(|app| Some(&app.app_name) == self.app_name.as_ref())
+            .collect();
+        if focused.is_empty() {
+            manifest.releases.iter().collect()
+        } else {
+            focused
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use std::process::Command;
+
+use camino::Utf8Path;
+use globset::Glob;
+
+use crate::data::release::parse_tag_version;
+use crate::errors::*;
+
+/// Separates the fields of a single tag in the `git for-each-ref` output
+const FIELD_SEPARATOR: char = '\u{1f}';
+/// Separates the tags in the `git for-each-ref` output
+const RECORD_SEPARATOR: char = '\u{1e}';
+/// The format we ask `git for-each-ref` to print each tag in
+///
+/// `creatordate` is the tagger date for annotated tags and the commit date for lightweight ones.
+const TAG_FORMAT: &str = "%(refname:strip=2)%1f%(objecttype)%1f%(creatordate:iso-strict)%1f%(contents:subject)%1f%(contents:body)%1e";
+
+/// A release read from a tag in the local git repository
+#[derive(Clone, Debug)]
+pub struct GitTagRelease {
+    /// The name of the tag
+    pub tag_name: String,
+    /// The message of the tag, if it was an annotated tag
+    pub message: Option<String>,
+    /// The date the tag was created (RFC 3339)
+    pub date: Option<String>,
+    /// Whether the tag parses as a semver prerelease
+    pub prerelease: bool,
+}
+
+impl GitTagRelease {
+    /// Read all the tags matching the given glob from the git repository at `repo_dir`,
+    /// from newest to oldest.
+    pub fn fetch_all(repo_dir: &Utf8Path, pattern: &str) -> Result<Vec<GitTagRelease>> {
+        let matcher = Glob::new(pattern)
+            .map_err(|e| OrandaError::InvalidGlob {
+                pattern: pattern.to_owned(),
+                details: e,
+            })?
+            .compile_matcher();
+
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(repo_dir)
+            .arg("for-each-ref")
+            .arg("--sort=-creatordate")
+            .arg(format!("--format={TAG_FORMAT}"))
+            .arg("refs/tags")
+            .output()
+            .map_err(|e| OrandaError::GitTagsFetchError {
+                details: e.to_string(),
+            })?;
+        if !output.status.success() {
+            return Err(OrandaError::GitTagsFetchError {
+                details: String::from_utf8_lossy(&output.stderr).trim().to_owned(),
+            });
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let mut releases = vec![];
+        for record in stdout.split(RECORD_SEPARATOR) {
+            let record = record.trim_start_matches('\n');
+            if record.is_empty() {
+                continue;
+            }
+            let fields: Vec<&str> = record.split(FIELD_SEPARATOR).collect();
+            let [tag_name, object_type, date, subject, body] = fields[..] else {
+                tracing::warn!("Skipping unexpected git tag output: {record:?}");
+                continue;
+            };
+            if !matcher.is_match(tag_name) {
+                continue;
+            }
+
+            // Lightweight tags point directly at a commit, so their "contents" is just
+            // the commit message, which isn't really release notes.
+            let message = if object_type == "tag" {
+                let message = format!("{}\n\n{}", subject.trim(), body.trim());
+                let message = message.trim();
+                (!message.is_empty()).then(|| message.to_owned())
+            } else {
+                None
+            };
+            let date = (!date.is_empty()).then(|| date.to_owned());
+            let prerelease = parse_tag_version(tag_name)
+                .map(|v| !v.pre.is_empty())
+                .unwrap_or(false);
+
+            releases.push(GitTagRelease {
+                tag_name: tag_name.to_owned(),
+                message,
+                date,
+                prerelease,
+            });
+        }
+
+        Ok(releases)
+    }
+}
@@ -7,6 +7,7 @@ mod repo;
 pub use repo::GithubRepo;
 
 use super::artifacts::{File, ReleaseArtifacts};
+use super::cache::HttpCache;
 
 /// From the GitHub Rest API
 /// as documented here: <https://docs.github.com/en/rest/releases/releases?apiVersion=2022-11-28>
@@ -48,18 +49,20 @@ pub struct GithubReleaseAsset {
 }
 
 impl GithubRelease {
-    pub async fn fetch_all(repo: &GithubRepo) -> Result<Vec<GithubRelease>> {
+    pub async fn fetch_all(repo: &GithubRepo, cache: &HttpCache) -> Result<Vec<GithubRelease>> {
         let request = octolotl::request::Releases::new(&repo.owner, &repo.name);
-        match octolotl::Request::send(&request, true).await {
+        match cache.fetch_github(&request).await {
             Ok(r) => {
-                let res: serde_json::Value = serde_json::from_str(&r.text().await?)?;
+                let res: serde_json::Value = serde_json::from_str(&r)?;
                 let pretty_response = serde_json::to_string_pretty(&res)?;
                 Ok(
                     SourceFile::new("", pretty_response)
                         .deserialize_json::<Vec<GithubRelease>>()?,
                 )
             }
-            Err(e) => Err(OrandaError::GithubReleasesFetchError { details: e }),
+            Err(e) => Err(OrandaError::GithubReleasesFetchError {
+                details: Box::new(e),
+            }),
         }
     }
 
@@ -83,9 +86,14 @@ impl ReleaseArtifacts {
             let file = File {
                 name: asset.name.clone(),
                 download_url: asset.browser_download_url.clone(),
+                size: u64::try_from(asset.size).ok(),
+                updated_at: Some(asset.updated_at.clone()),
+                download_count: u64::try_from(asset.download_count).ok(),
                 // The rest of these are filled in later
                 view_path: None,
                 checksum_file: None,
+                sha256: None,
+                companion_files: vec![],
                 infer: true,
             };
             self.add_file(file);
@@ -44,4 +44,12 @@ impl GithubRepo {
             details: miette!("This URL has less parts than we expected"),
         })
     }
+
+    /// Get the url a release asset is (or will be) downloadable from
+    pub fn release_asset_url(&self, tag: &str, asset_name: &str) -> String {
+        format!(
+            "https://github.com/{}/{}/releases/download/{tag}/{asset_name}",
+            self.owner, self.name
+        )
+    }
 }
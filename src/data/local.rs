@@ -0,0 +1,83 @@
+//! Logic for release artifacts in a local directory
+//!
+//! Some projects don't publish releases to a forge at all. Instead, CI builds the artifacts
+//! (e.g. into `target/distrib/` with cargo-dist) and they're hosted along with the site.
+//! We add those files to the release just like the assets of a Github Release, so cargo-dist
+//! and inference work the same as they do for those, and they're copied into the site when
+//! it's written.
+
+use camino::{Utf8Path, Utf8PathBuf};
+use chrono::{DateTime, Utc};
+
+use crate::config::Config;
+use crate::data::artifacts::{File, LocalFile, ReleaseArtifacts};
+use crate::data::cargo_dist::MANIFEST_FILENAME;
+use crate::errors::*;
+use crate::message::{Message, MessageType};
+use crate::site::link;
+
+/// Where local artifacts go in the site (followed by the release's version tag)
+pub const LOCAL_ARTIFACTS_PATH: &str = "download";
+
+impl ReleaseArtifacts {
+    /// Add the files in a local directory to the release, to be hosted with the site
+    ///
+    /// Returns the contents of the directory's dist-manifest.json, if it has one.
+    pub fn add_local_dir(
+        &mut self,
+        dir: &Utf8Path,
+        tag: &str,
+        config: &Config,
+    ) -> Result<Option<String>> {
+        if !dir.is_dir() {
+            let msg = format!(
+                "Your artifacts directory ({dir}) doesn't exist, so there won't be any downloads. Did you forget to build them?"
+            );
+            Message::new(MessageType::Warning, &msg).print();
+            return Ok(None);
+        }
+        let site_dir = format!("{LOCAL_ARTIFACTS_PATH}/{tag}");
+
+        // Directories are listed in whatever order the OS likes, so sort them to be consistent
+        let mut entries = dir.read_dir_utf8()?.collect::<std::io::Result<Vec<_>>>()?;
+        entries.sort_by(|a, b| a.file_name().cmp(b.file_name()));
+
+        let mut manifest = None;
+        for entry in entries {
+            let metadata = entry.metadata()?;
+            if !metadata.is_file() {
+                continue;
+            }
+            let name = entry.file_name().to_owned();
+            if name == MANIFEST_FILENAME {
+                manifest = Some(std::fs::read_to_string(entry.path())?);
+            }
+
+            let site_path = Utf8PathBuf::from(format!("{site_dir}/{name}"));
+            let download_url = link::generate(&config.build.path_prefix, site_path.as_str());
+            let updated_at = metadata
+                .modified()
+                .ok()
+                .map(|modified| DateTime::<Utc>::from(modified).to_rfc3339());
+            let file = File {
+                name,
+                download_url,
+                size: Some(metadata.len()),
+                updated_at,
+                // The rest of these are filled in later
+                download_count: None,
+                view_path: None,
+                checksum_file: None,
+                sha256: None,
+                companion_files: vec![],
+                infer: true,
+            };
+            let local_file = LocalFile {
+                path: entry.path().to_owned(),
+                site_path,
+            };
+            self.add_local_file(file, local_file);
+        }
+        Ok(manifest)
+    }
+}
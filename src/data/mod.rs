@@ -1,18 +1,30 @@
-use crate::config::{ArtifactsConfig, ProjectConfig};
+use std::cmp::Reverse;
+
+use camino::Utf8Path;
+use chrono::DateTime;
+use globset::{Glob, GlobSet, GlobSetBuilder};
+
+use crate::config::{Config, ReleaseSort, ReleasesConfig, ReleasesSource};
+use crate::data::cache::HttpCache;
+use crate::data::git::GitTagRelease;
 use crate::data::github::{GithubRelease, GithubRepo};
-use crate::data::release::CurrentStateRelease;
+use crate::data::release::parse_tag_version;
 use crate::errors::*;
 use crate::message::{Message, MessageType};
 
 pub mod artifacts;
+pub mod cache;
 pub mod cargo_dist;
 pub mod funding;
+pub mod git;
 pub mod github;
+pub mod local;
 mod release;
 
-pub use release::Release;
+pub use release::{CurrentStateRelease, Release, ReleaseSource};
 
-use self::release::ReleaseSource;
+/// The release channel we show by default, if the user has one with this name
+pub const DEFAULT_CHANNEL: &str = "stable";
 
 #[derive(Debug)]
 pub struct Context {
@@ -31,37 +43,79 @@ pub struct Context {
     /// Whether any of the `releases` have anything useful for
     /// the artifacts subsystem.
     pub has_artifacts: bool,
+    /// The latest release of each release channel the user configured
+    ///
+    /// (empty if they didn't configure any, or none of them have releases)
+    pub channels: Vec<ReleaseChannel>,
+}
+
+/// The latest release of a release channel
+#[derive(Debug, Clone)]
+pub struct ReleaseChannel {
+    /// The name of the channel (e.g. "stable")
+    pub name: String,
+    /// Index into `releases` for the latest release in this channel
+    pub latest_release: usize,
 }
 
 impl Context {
+    /// Get releases from wherever the config says they come from
+    pub fn new(config: &Config) -> Result<Self> {
+        match (config.releases.source, &config.project.repository) {
+            (Some(ReleasesSource::GitTags), _) => Self::new_git_tags(config),
+            (Some(ReleasesSource::Github) | None, Some(repo_url)) => {
+                Self::new_github(repo_url, config)
+            }
+            (Some(ReleasesSource::Github), None) => Err(OrandaError::ReleasesSourceNeedsRepo),
+            (None, None) => Self::new_current(config),
+        }
+    }
     /// Make a Context with a faux-release for the current project state
-    pub fn new_current(
-        project_config: &ProjectConfig,
-        artifacts_config: Option<&ArtifactsConfig>,
-    ) -> Result<Self> {
-        let releases = tokio::runtime::Handle::current().block_on(Self::make_current_release(
-            None,
-            project_config,
-            artifacts_config,
-        ))?;
-        Ok(Self::with_releases(None, releases, artifacts_config))
+    pub fn new_current(config: &Config) -> Result<Self> {
+        let releases =
+            tokio::runtime::Handle::current().block_on(Self::make_current_release(None, config))?;
+        Self::with_releases(None, releases, config)
     }
     /// Get releases using github
-    pub fn new_github(
-        repo_url: &str,
-        project_config: &ProjectConfig,
-        artifacts_config: Option<&ArtifactsConfig>,
-    ) -> Result<Self> {
+    pub fn new_github(repo_url: &str, config: &Config) -> Result<Self> {
         let repo = GithubRepo::from_url(repo_url)?;
-        let mut releases = Self::fetch_all_releases(&repo, artifacts_config)?;
+        let mut releases = if config.build.cache_mode.is_offline() {
+            // Use whatever releases we have cached, or just describe the current state
+            match Self::fetch_all_releases(&repo, config) {
+                Ok(releases) => {
+                    let msg = "We're offline, so your Github Releases may be out of date (we're using a cached copy).";
+                    Message::new(MessageType::Warning, msg).print();
+                    releases
+                }
+                Err(_) => {
+                    let msg = "We're offline and don't have your Github Releases cached, so we're only describing the current state of your project.";
+                    Message::new(MessageType::Warning, msg).print();
+                    vec![]
+                }
+            }
+        } else {
+            Self::fetch_all_releases(&repo, config)?
+        };
         if releases.is_empty() {
-            releases = tokio::runtime::Handle::current().block_on(Self::make_current_release(
-                Some(&repo),
-                project_config,
-                artifacts_config,
-            ))?;
+            releases = tokio::runtime::Handle::current()
+                .block_on(Self::make_current_release(Some(&repo), config))?;
         }
-        Ok(Self::with_releases(Some(repo), releases, artifacts_config))
+        Self::with_releases(Some(repo), releases, config)
+    }
+    /// Get releases from the tags of the local git repository
+    pub fn new_git_tags(config: &Config) -> Result<Self> {
+        // If there's a github repo we can still use it for things like the repo banner
+        let repo = config
+            .project
+            .repository
+            .as_ref()
+            .and_then(|url| GithubRepo::from_url(url).ok());
+        let mut releases = Self::fetch_git_tag_releases(Utf8Path::new("."), config)?;
+        if releases.is_empty() {
+            releases = tokio::runtime::Handle::current()
+                .block_on(Self::make_current_release(repo.as_ref(), config))?;
+        }
+        Self::with_releases(repo, releases, config)
     }
 
     /// Get the latest release, if it exists
@@ -69,6 +123,16 @@ impl Context {
         self.latest_release.and_then(|idx| self.releases.get(idx))
     }
 
+    /// Get the release channel that's shown by default, if any were configured
+    ///
+    /// This is the "stable" channel if there is one, and otherwise the first one.
+    pub fn default_channel(&self) -> Option<&ReleaseChannel> {
+        self.channels
+            .iter()
+            .find(|channel| channel.name == DEFAULT_CHANNEL)
+            .or_else(|| self.channels.first())
+    }
+
     /// Mutably get the latest release, if it exists
     pub fn latest_mut(&mut self) -> Option<&mut Release> {
         self.latest_release
@@ -76,35 +140,92 @@ impl Context {
     }
 
     /// Fetch and process all the Github Releases to produce a final result
-    pub fn fetch_all_releases(
-        repo: &GithubRepo,
-        artifacts_config: Option<&ArtifactsConfig>,
-    ) -> Result<Vec<Release>> {
+    ///
+    /// Releases the user asked us to ignore are dropped before we fetch anything else for them.
+    pub fn fetch_all_releases(repo: &GithubRepo, config: &Config) -> Result<Vec<Release>> {
+        let cache = HttpCache::new(config.build.cache_mode);
         let gh_releases =
-            tokio::runtime::Handle::current().block_on(GithubRelease::fetch_all(repo))?;
-        let all = tokio::runtime::Handle::current().block_on(
-            futures_util::future::try_join_all(gh_releases.into_iter().map(|gh_release| {
-                Release::new(
-                    ReleaseSource::Github(gh_release),
-                    Some(repo),
-                    artifacts_config,
-                )
-            })),
-        )?;
+            tokio::runtime::Handle::current().block_on(GithubRelease::fetch_all(repo, &cache))?;
+        let sources = gh_releases.into_iter().map(ReleaseSource::Github).collect();
+        let sources = Self::filter_and_sort_releases(sources, &config.releases)?;
+        let all =
+            tokio::runtime::Handle::current().block_on(futures_util::future::try_join_all(
+                sources
+                    .into_iter()
+                    .map(|source| Release::new(source, Some(repo), config)),
+            ))?;
         Ok(all)
     }
 
-    fn with_releases(
+    /// Process the tags of a local git repository to produce a final result
+    ///
+    /// Tags the user asked us to ignore are dropped before we look at anything else for them.
+    pub fn fetch_git_tag_releases(repo_dir: &Utf8Path, config: &Config) -> Result<Vec<Release>> {
+        let tags = GitTagRelease::fetch_all(repo_dir, &config.releases.git_tag_pattern)?;
+        let sources = tags.into_iter().map(ReleaseSource::GitTag).collect();
+        let sources = Self::filter_and_sort_releases(sources, &config.releases)?;
+        let all =
+            tokio::runtime::Handle::current().block_on(futures_util::future::try_join_all(
+                sources
+                    .into_iter()
+                    .map(|source| Release::new(source, None, config)),
+            ))?;
+        Ok(all)
+    }
+
+    /// Drop any releases the user asked us to ignore, and sort the rest from newest to oldest
+    ///
+    /// This works on finished releases as well as just their sources, so that we can skip
+    /// ignored releases before doing any work on them.
+    pub fn filter_and_sort_releases<R: AsRef<ReleaseSource>>(
+        releases: Vec<R>,
+        releases_config: &ReleasesConfig,
+    ) -> Result<Vec<R>> {
+        let include = build_glob_set(&releases_config.include)?;
+        let exclude = build_glob_set(&releases_config.exclude)?;
+        let mut releases: Vec<R> = releases
+            .into_iter()
+            .filter(|release| {
+                let tag = release.as_ref().version_tag();
+                let included = releases_config.include.is_empty() || include.is_match(tag);
+                let excluded = exclude.is_match(tag);
+                if !included || excluded {
+                    tracing::info!("Ignoring release {tag} because of your release filters");
+                }
+                included && !excluded
+            })
+            .collect();
+
+        // These sorts are stable, so anything we can't make sense of keeps its original
+        // position relative to the others, after everything we can.
+        match releases_config.sort {
+            ReleaseSort::Date => {
+                releases.sort_by_cached_key(|release| {
+                    let date = release
+                        .as_ref()
+                        .date()
+                        .and_then(|date| DateTime::parse_from_rfc3339(date).ok());
+                    Reverse(date)
+                });
+            }
+            ReleaseSort::Semver => {
+                releases.sort_by_cached_key(|release| {
+                    Reverse(parse_tag_version(release.as_ref().version_tag()))
+                });
+            }
+        }
+
+        Ok(releases)
+    }
+
+    /// Process the final list of releases (from newest to oldest) into a Context
+    pub fn with_releases(
         repo: Option<GithubRepo>,
         releases: Vec<Release>,
-        artifacts_config: Option<&ArtifactsConfig>,
-    ) -> Self {
+        config: &Config,
+    ) -> Result<Self> {
+        let artifacts_config = config.components.artifacts.as_ref();
         // Walk through all the releases (from newest to oldest) to find the latest ones
-        //
-        // FIXME?: I think this is essentially deferring to Release Date over Version Number.
-        // In most cases those things are in agreement, but anyone who does patch releases
-        // of older versions will have a more chaotic history. When we have more robust
-        // handling of release tags we should try to sort/filter this better.
         let mut has_prereleases = false;
         let mut has_artifacts = false;
         let mut warned = false;
@@ -158,9 +279,11 @@ impl Context {
         // If we found a stable cargo-dist release, but there's even newer stable releases
         // that don't use cargo-dist, we're going to prefer the cargo-dist one, but we should
         // warn the user that things are wonky
-        if let (Some(dist_latest), Some(latest)) =
-            (latest_dist_stable_release, latest_stable_release)
-        {
+        if let (Some(dist_latest), Some(latest), false) = (
+            latest_dist_stable_release,
+            latest_stable_release,
+            config.releases.has_filters(),
+        ) {
             if latest_dist_stable_release != latest_stable_release {
                 let dist_rel = &releases[dist_latest].source.version_tag();
                 let stable_rel = &releases[latest].source.version_tag();
@@ -177,38 +300,100 @@ impl Context {
         // * normal prerelease
         //
         // The reason we single out dist releases as special/better is a bit of
-        // a legacy hack to keep things working while we don't have a good mechanism for filtering
-        // out "other" github release like oranda-css. In the future we will only care about
-        // stable vs unstable.
-        let latest_release = latest_dist_stable_release
-            .or(latest_stable_release)
-            .or(latest_dist_prerelease)
-            .or(latest_prerelease);
-
-        Self {
+        // a legacy hack to keep things working for people who haven't told us how to filter
+        // out "other" github release like oranda-css. If they have configured filters,
+        // we trust those and only care about stable vs unstable.
+        let latest_release = if config.releases.has_filters() {
+            latest_stable_release.or(latest_prerelease)
+        } else {
+            latest_dist_stable_release
+                .or(latest_stable_release)
+                .or(latest_dist_prerelease)
+                .or(latest_prerelease)
+        };
+
+        let mut context = Self {
             repo,
             releases,
             has_prereleases,
             has_artifacts,
             latest_release,
+            channels: vec![],
+        };
+
+        // If the user has release channels, the default one decides what the latest release is
+        context.channels = Self::find_channels(&context.releases, &config.releases)?;
+        if let Some(channel) = context.default_channel() {
+            context.latest_release = Some(channel.latest_release);
         }
+
+        Ok(context)
+    }
+
+    /// Find the latest release (from newest to oldest) of each release channel
+    ///
+    /// Channels without any releases are left out.
+    fn find_channels(
+        releases: &[Release],
+        releases_config: &ReleasesConfig,
+    ) -> Result<Vec<ReleaseChannel>> {
+        let mut channels = vec![];
+        for (name, channel_config) in &releases_config.channels {
+            let tags = build_glob_set(&channel_config.tags)?;
+            let latest_release = releases.iter().position(|release| {
+                let prerelease_matches = channel_config
+                    .prerelease
+                    .map(|prerelease| prerelease == release.source.is_prerelease())
+                    .unwrap_or(true);
+                let tag_matches =
+                    channel_config.tags.is_empty() || tags.is_match(release.source.version_tag());
+                prerelease_matches && tag_matches
+            });
+            if let Some(latest_release) = latest_release {
+                channels.push(ReleaseChannel {
+                    name: name.clone(),
+                    latest_release,
+                });
+            } else {
+                let msg = format!(
+                    "We didn't find any releases in the {name} release channel, so it won't be shown."
+                );
+                Message::new(MessageType::Warning, &msg).print();
+            }
+        }
+        Ok(channels)
     }
 
     async fn make_current_release(
         repo: Option<&GithubRepo>,
-        project_config: &ProjectConfig,
-        artifacts_config: Option<&ArtifactsConfig>,
+        config: &Config,
     ) -> Result<Vec<Release>> {
         let release = Release::new(
             ReleaseSource::CurrentState(CurrentStateRelease {
-                version: project_config.version.to_owned(),
+                version: config.project.version.to_owned(),
                 date: None,
                 prerelease: false,
             }),
             repo,
-            artifacts_config,
+            config,
         )
         .await?;
         Ok(vec![release])
     }
 }
+
+/// Compile a list of globs into one matcher
+pub(crate) fn build_glob_set(patterns: &[String]) -> Result<GlobSet> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let glob = Glob::new(pattern).map_err(|e| OrandaError::InvalidGlob {
+            pattern: pattern.clone(),
+            details: e,
+        })?;
+        builder.add(glob);
+    }
+    builder.build().map_err(|e| OrandaError::InvalidGlob {
+        pattern: patterns.join(", "),
+        details: e,
+    })
+}
@@ -1,10 +1,13 @@
-use axoasset::SourceFile;
-use cargo_dist_schema::DistManifest;
+use axoasset::{LocalAsset, SourceFile};
+use camino::Utf8Path;
 use chrono::DateTime;
 
-use crate::config::ArtifactsConfig;
-use crate::data::{cargo_dist, github::GithubRelease, GithubRepo};
+use crate::config::Config;
+use crate::data::cache::HttpCache;
+use crate::data::cargo_dist::{self, DistManifest, ManifestRead};
+use crate::data::{git::GitTagRelease, github::GithubRelease, GithubRepo};
 use crate::errors::*;
+use crate::message::{Message, MessageType};
 
 use super::artifacts::ReleaseArtifacts;
 
@@ -12,6 +15,7 @@ use super::artifacts::ReleaseArtifacts;
 #[derive(Debug, Clone)]
 pub enum ReleaseSource {
     Github(GithubRelease),
+    GitTag(GitTagRelease),
     CurrentState(CurrentStateRelease),
 }
 
@@ -29,6 +33,7 @@ impl ReleaseSource {
     pub fn version_tag(&self) -> &str {
         match self {
             ReleaseSource::Github(src) => &src.tag_name,
+            ReleaseSource::GitTag(src) => &src.tag_name,
             ReleaseSource::CurrentState(src) => src.version.as_deref().unwrap_or("current"),
         }
     }
@@ -37,6 +42,7 @@ impl ReleaseSource {
     pub fn is_prerelease(&self) -> bool {
         match self {
             ReleaseSource::Github(src) => src.prerelease,
+            ReleaseSource::GitTag(src) => src.prerelease,
             ReleaseSource::CurrentState(src) => src.prerelease,
         }
     }
@@ -45,6 +51,7 @@ impl ReleaseSource {
     pub fn date(&self) -> Option<&str> {
         match self {
             ReleaseSource::Github(src) => Some(src.published_at.as_str()),
+            ReleaseSource::GitTag(src) => src.date.as_deref(),
             ReleaseSource::CurrentState(src) => src.date.as_deref(),
         }
     }
@@ -64,6 +71,7 @@ impl ReleaseSource {
     pub fn name(&self) -> Option<&str> {
         match self {
             ReleaseSource::Github(src) => src.name.as_deref(),
+            ReleaseSource::GitTag(_src) => None,
             ReleaseSource::CurrentState(_src) => None,
         }
     }
@@ -72,11 +80,25 @@ impl ReleaseSource {
     pub(crate) fn body(&self) -> Option<&str> {
         match self {
             ReleaseSource::Github(src) => src.body.as_deref(),
+            ReleaseSource::GitTag(src) => src.message.as_deref(),
             ReleaseSource::CurrentState(_src) => None,
         }
     }
 }
 
+/// Try to find a semver version in a tag like "0.1.0", "v0.1.0", or "css-v0.1.0"
+pub fn parse_tag_version(tag: &str) -> Option<semver::Version> {
+    tag.char_indices()
+        .filter(|(_, c)| c.is_ascii_digit())
+        .find_map(|(idx, _)| semver::Version::parse(&tag[idx..]).ok())
+}
+
+impl AsRef<ReleaseSource> for ReleaseSource {
+    fn as_ref(&self) -> &ReleaseSource {
+        self
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct Release {
     pub manifest: Option<DistManifest>,
@@ -84,43 +106,95 @@ pub struct Release {
     pub artifacts: ReleaseArtifacts,
 }
 
+impl AsRef<ReleaseSource> for Release {
+    fn as_ref(&self) -> &ReleaseSource {
+        &self.source
+    }
+}
+
 impl Release {
     pub async fn new(
         source: ReleaseSource,
         repo: Option<&GithubRepo>,
-        artifacts_config: Option<&ArtifactsConfig>,
+        config: &Config,
     ) -> Result<Self> {
         // If artifacts are disabled then bail out, because all this code is just artifacts stuff
-        let Some(artifacts_config) = artifacts_config else {
+        let Some(artifacts_config) = config.components.artifacts.as_ref() else {
             return Ok(Self { manifest: None, source, artifacts: ReleaseArtifacts::new(None)} )
         };
 
-        let manifest = if let (ReleaseSource::Github(gh_release), Some(repo)) = (&source, repo) {
+        let mut manifest = if let (ReleaseSource::Github(gh_release), Some(repo)) = (&source, repo)
+        {
             if artifacts_config.cargo_dist {
-                Self::fetch_manifest(gh_release, repo).await?
+                let cache = HttpCache::new(config.build.cache_mode);
+                match Self::fetch_manifest(gh_release, repo, &cache).await {
+                    Ok(manifest) => manifest,
+                    Err(OrandaError::OfflineCacheMiss { .. }) => {
+                        let msg = format!("We're offline and don't have the dist-manifest.json for {} cached, so its cargo-dist artifacts will be missing.", gh_release.tag_name);
+                        Message::new(MessageType::Warning, &msg).print();
+                        None
+                    }
+                    Err(e) => return Err(e),
+                }
             } else {
                 None
             }
+        } else if let (ReleaseSource::CurrentState(_), true, None) = (
+            &source,
+            artifacts_config.cargo_dist,
+            &artifacts_config.local_dir,
+        ) {
+            // There's no release yet, but cargo-dist may have built the upcoming one
+            // (if there's a local directory of artifacts, we use the manifest in there instead)
+            Self::load_local_manifest(&artifacts_config.dist_manifest_path, source.version_tag())
+                .await?
         } else {
-            // FIXME: warn if cargo-dist enabled?
             None
         };
 
-        // Compute the artifacts for this release
-        //
-        // In the future with multi-tenant oranda support, this None
-        // can be replaced with the name of the app we want to focus in on
-        let mut artifacts = ReleaseArtifacts::new(None);
+        // Compute the artifacts for this release, focusing on the app this site is about
+        let app_name = artifacts_config
+            .app_name
+            .clone()
+            .or_else(|| Some(config.project.name.clone()))
+            .filter(|name| !name.is_empty());
+        let mut artifacts = ReleaseArtifacts::new(app_name);
 
         // Add data from various sources
         if let ReleaseSource::Github(gh_release) = &source {
             artifacts.add_github(gh_release);
         }
+        if let (ReleaseSource::CurrentState(_), Some(local_dir)) =
+            (&source, &artifacts_config.local_dir)
+        {
+            let tag = source.version_tag();
+            let local_manifest = artifacts.add_local_dir(Utf8Path::new(local_dir), tag, config)?;
+            if let (Some(local_manifest), true) = (local_manifest, artifacts_config.cargo_dist) {
+                manifest = Self::parse_response(local_manifest, tag).await?;
+            }
+        }
+        if let (ReleaseSource::CurrentState(_), Some(manifest)) = (&source, &manifest) {
+            artifacts.add_unreleased_cargo_dist(manifest, repo, source.version_tag());
+        }
         if let Some(manifest) = &manifest {
-            artifacts.add_cargo_dist(manifest);
+            artifacts.add_cargo_dist(manifest, source.version_tag());
         }
         artifacts.add_package_managers(artifacts_config);
+        let version = parse_tag_version(source.version_tag());
+        artifacts.add_auto_package_managers(artifacts_config, version.as_ref());
+        artifacts.add_package_manifests(artifacts_config, repo);
         artifacts.add_inference();
+        artifacts.apply_run_hints(artifacts_config);
+        artifacts.apply_file_config(artifacts_config)?;
+        // Files of an unreleased version will be published under the manifest's tag
+        let tag = match (&source, &manifest) {
+            (ReleaseSource::CurrentState(_), Some(manifest)) => manifest
+                .announcement_tag
+                .as_deref()
+                .unwrap_or(source.version_tag()),
+            _ => source.version_tag(),
+        };
+        artifacts.apply_mirror(artifacts_config, tag, repo);
 
         // Compute the final result
         artifacts.select_installers(artifacts_config);
@@ -140,6 +214,7 @@ impl Release {
     async fn fetch_manifest(
         gh_release: &GithubRelease,
         repo: &GithubRepo,
+        cache: &HttpCache,
     ) -> Result<Option<DistManifest>> {
         let tag = &gh_release.tag_name;
         if gh_release.has_dist_manifest() {
@@ -149,9 +224,7 @@ impl Release {
                 tag,
                 cargo_dist::MANIFEST_FILENAME,
             );
-            let response = octolotl::Request::send(&request, true)
-                .await?
-                .error_for_status()?;
+            let response = cache.fetch_github(&request).await?;
 
             Ok(Self::parse_response(response, tag).await?)
         } else {
@@ -159,39 +232,37 @@ impl Release {
         }
     }
 
-    async fn parse_response(
-        response: reqwest::Response,
-        tag: &str,
-    ) -> Result<Option<DistManifest>> {
-        let res = response.text().await?;
-        let src = SourceFile::new("dist-manifest.json", res);
-        Ok(match src.deserialize_json::<DistManifest>() {
-            Ok(manifest) => Some(manifest),
-            Err(e) => {
-                // Try partially parsing the manifest to get schema version info
-                let info = cargo_dist_schema::check_version(src.contents());
-                if let Some(info) = info {
-                    if info.format.unsupported() {
-                        // Don't mention it -- nothing's wrong, it's just too old
-                    } else {
-                        let schema_version = info.version.to_string();
-                        let parser_version = cargo_dist_schema::SELF_VERSION.to_owned();
-                        let tag = tag.to_owned();
-                        let err = OrandaError::CargoDistManifestPartial {
-                            schema_version,
-                            parser_version,
-                            tag,
-                            details: e,
-                        };
-                        let report = miette::Report::new(err);
-                        eprintln!("{report:?}");
-                    }
-                } else {
-                    let tag = tag.to_owned();
-                    let err = OrandaError::CargoDistManifestMalformed { tag, details: e };
-                    let report = miette::Report::new(err);
-                    eprintln!("{report:?}");
+    async fn load_local_manifest(path: &str, tag: &str) -> Result<Option<DistManifest>> {
+        if !Utf8Path::new(path).exists() {
+            let msg = format!("cargo-dist is enabled, but there are no releases yet and no dist-manifest.json at {path}, so there won't be any downloads. (Building your release with cargo-dist will let you preview them.)");
+            Message::new(MessageType::Warning, &msg).print();
+            return Ok(None);
+        }
+        let contents = LocalAsset::load_string(path)?;
+        Self::parse_response(contents, tag).await
+    }
+
+    async fn parse_response(response: String, tag: &str) -> Result<Option<DistManifest>> {
+        let src = SourceFile::new("dist-manifest.json", response);
+        Ok(match DistManifest::read(&src) {
+            ManifestRead::Read { manifest, skipped } => {
+                // Whatever we couldn't read is probably from a newer/older cargo-dist
+                if !skipped.is_empty() {
+                    let dist_version = manifest
+                        .dist_version
+                        .as_deref()
+                        .unwrap_or("(unknown version)");
+                    let msg = format!(
+                        "Skipping parts of the dist-manifest.json for {tag} that we couldn't read. The manifest was made by cargo-dist {dist_version}, and we skipped: {}",
+                        skipped.join(", ")
+                    );
+                    Message::new(MessageType::Warning, &msg).print();
                 }
+                Some(manifest)
+            }
+            ManifestRead::Malformed(e) => {
+                let msg = format!("Skipping malformed dist-manifest.json for {tag}: {e}");
+                Message::new(MessageType::Warning, &msg).print();
                 None
             }
         })
@@ -1,4 +1,3 @@
-use axoasset::AxoassetError;
 use camino::Utf8PathBuf;
 use miette::Diagnostic;
 use thiserror::Error;
@@ -47,15 +46,44 @@ pub enum OrandaError {
     #[error("Failed fetching releases from Github.")]
     GithubReleasesFetchError {
         #[source]
-        details: octolotl::OctolotlError,
+        details: Box<OrandaError>,
     },
 
+    #[error("Couldn't fetch {url} because we're offline, and it isn't in the cache")]
+    #[diagnostic(help(
+        "Run a build without --offline once (with network access) to fill the cache."
+    ))]
+    OfflineCacheMiss { url: String },
+
+    #[error("Couldn't find any oranda CSS to use while offline")]
+    #[diagnostic(help(
+        "This build of oranda doesn't include its CSS, and it isn't in the cache. Run a build without --offline once, or point the ORANDA_CSS environment variable at a copy of oranda.css."
+    ))]
+    OfflineNoCss,
+
     #[error("Failed parsing response when fetching releases from Github.")]
     GithubReleaseParseError {
         #[source]
         details: axoasset::AxoassetError,
     },
 
+    #[error("Failed reading tags from the local git repository: {details}")]
+    #[diagnostic(help(
+        "Make sure git is installed and that you're running oranda inside a git repository."
+    ))]
+    GitTagsFetchError { details: String },
+
+    #[error("Your releases source is `github`, but you haven't configured a `repository`")]
+    #[diagnostic(help("Set `project.repository` in your oranda.json, or pick another source."))]
+    ReleasesSourceNeedsRepo,
+
+    #[error("Invalid glob pattern `{pattern}`")]
+    InvalidGlob {
+        pattern: String,
+        #[source]
+        details: globset::Error,
+    },
+
     #[error("Could not find any releases from {repo_owner}/{repo_name} with a cargo-dist compatible `dist-manifest.json`.")]
     NoCargoDistReleasesFound {
         repo_owner: String,
@@ -80,27 +108,6 @@ pub enum OrandaError {
     #[diagnostic(help("Did you remember to run `oranda build`?"))]
     BuildNotFound { dist_dir: String },
 
-    #[error("Skipping malformed dist-manifest.json for {tag}")]
-    #[diagnostic(severity = "warn")]
-    CargoDistManifestMalformed {
-        tag: String,
-        #[diagnostic_source]
-        details: AxoassetError,
-    },
-
-    #[error("Skipping unparseable dist-manifest.json for {tag}")]
-    #[diagnostic(help(
-        "the schema was version {schema_version}, while our parser is version {parser_version}"
-    ))]
-    #[diagnostic(severity = "warn")]
-    CargoDistManifestPartial {
-        schema_version: String,
-        parser_version: String,
-        tag: String,
-        #[diagnostic_source]
-        details: AxoassetError,
-    },
-
     #[error("Couldn't load your mdbook at {path}")]
     MdBookLoad {
         path: String,
@@ -5,15 +5,22 @@ use axohtml::{html, text, unsafe_text};
 use std::collections::HashMap;
 
 use crate::config::Config;
-use crate::data::artifacts::inference::triple_to_display_name;
-use crate::data::artifacts::{FileIdx, InstallMethod, InstallerIdx, TargetTriple};
+use crate::data::artifacts::inference::triple_to_readable_name;
+use crate::data::artifacts::{File, FileIdx, InstallMethod, Installer, InstallerIdx, TargetTriple};
 use crate::data::Release;
 use crate::errors::*;
 use crate::site::{icons, link, markdown};
 
 type Platforms = HashMap<TargetTriple, Vec<InstallerIdx>>;
 
-pub fn build_header(release: &Release, config: &Config) -> Result<Box<div<String>>> {
+/// Build the install widget for a release
+///
+/// If `link_targets` is set, each platform links to its page from [`build_target_page`][].
+pub fn build_header(
+    release: &Release,
+    config: &Config,
+    link_targets: bool,
+) -> Result<Box<div<String>>> {
     let downloads_href = link::generate(&config.build.path_prefix, "artifacts/");
     let tag = release.source.version_tag();
     let platforms_we_want = filter_platforms(release);
@@ -24,7 +31,7 @@ pub fn build_header(release: &Release, config: &Config) -> Result<Box<div<String
         html!(<div><small class="published-date">{text!("Published on {}", date)}</small></div>)
     });
 
-    let arches = build_arches(&platforms_we_want, release, config);
+    let arches = build_arches(&platforms_we_want, release, config, link_targets);
     let selector = selector_html(&platforms_we_want);
 
     let main_html = html!(
@@ -41,9 +48,21 @@ pub fn build_header(release: &Release, config: &Config) -> Result<Box<div<String
     let noscript = if simple_platforms {
         None
     } else {
-        Some(
-            html!(<noscript><a href=&downloads_href class="backup-download primary">{text!("View all installation options")}</a></noscript>),
-        )
+        let target_links = link_targets.then(|| {
+            let mut targets: Vec<_> = platforms_we_want.keys().collect();
+            targets.sort_by_key(|target| triple_to_readable_name(target));
+            let links = targets.into_iter().map(|target| {
+                let href = target_page_href(target, config);
+                html!(<li><a href=href>{text!(triple_to_readable_name(target))}</a></li>)
+            });
+            html!(<ul class="target-links">{links}</ul>)
+        });
+        Some(html!(
+        <noscript>
+            {target_links}
+            <a href=&downloads_href class="backup-download primary">{text!("View all installation options")}</a>
+        </noscript>
+        ))
     };
     // If there's only one platform we don't need dropdowns
     let selector = if simple_platforms {
@@ -54,7 +73,7 @@ pub fn build_header(release: &Release, config: &Config) -> Result<Box<div<String
                 None
             } else {
                 // Otherwise mention the platform
-                let os_name = triple_to_display_name(target).unwrap();
+                let os_name = triple_to_readable_name(target);
                 let desc = format!("Platform: {os_name}");
                 Some(html!(<div class="arch-select">{text!(desc)}</div>))
             }
@@ -94,8 +113,59 @@ pub fn build_header(release: &Release, config: &Config) -> Result<Box<div<String
     ))
 }
 
+/// Build a page with every way to install a release on one platform
+///
+/// Everything is rendered up front, so unlike the header this works without javascript.
+pub fn build_target_page(
+    release: &Release,
+    target: &TargetTriple,
+    installers: &[InstallerIdx],
+    config: &Config,
+) -> Box<div<String>> {
+    let downloads_href = link::generate(&config.build.path_prefix, "artifacts/");
+    let tag = release.source.version_tag();
+    let os_name = triple_to_readable_name(target);
+    let date_html = release.source.formatted_date().map(|date| {
+        html!(<div><small class="published-date">{text!("Published on {}", date)}</small></div>)
+    });
+
+    let mut sections = vec![];
+    for idx in installers {
+        let installer = release.artifacts.installer(*idx);
+        let html = installer_html(installer, release, config);
+        let description = (!installer.description.is_empty())
+            .then(|| html!(<p class="installer-description">{text!(&installer.description)}</p>));
+        sections.push(html!(
+            <div class="target-installer">
+                <h3>{text!(&installer.label)}</h3>
+                {description}
+                {html}
+            </div>
+        ));
+    }
+
+    html!(
+    <div class="target-page" data-arch=target>
+        <h2>{text!("Install {} on {}", tag, os_name)}</h2>
+        {date_html}
+        {sections}
+        <a href=downloads_href class="backup-download primary">{text!("View all installation options")}</a>
+    </div>
+    )
+}
+
+/// Get the link to a platform's page from [`build_target_page`][]
+pub fn target_page_href(target: &TargetTriple, config: &Config) -> String {
+    link::generate(&config.build.path_prefix, &format!("artifacts/{target}/"))
+}
+
 /// Build the tab and content HTML for all arches.
-fn build_arches(platforms: &Platforms, release: &Release, config: &Config) -> Vec<Box<li<String>>> {
+fn build_arches(
+    platforms: &Platforms,
+    release: &Release,
+    config: &Config,
+    link_targets: bool,
+) -> Vec<Box<li<String>>> {
     let mut html = vec![];
     let simple_platforms = platforms.len() == 1;
 
@@ -111,6 +181,11 @@ fn build_arches(platforms: &Platforms, release: &Release, config: &Config) -> Ve
         };
 
         let contents = content_list(target, installers, release, config, simple_platforms);
+        // The universal "all" platform doesn't get its own page
+        let target_link = (link_targets && target != "all").then(|| {
+            let href = target_page_href(target, config);
+            html!(<a class="target-link" href=href>{text!("Link to these instructions")}</a>)
+        });
 
         // If there's only one entry, make it visible by default (noscript friendly)
         let classes = if simple_platforms {
@@ -124,6 +199,7 @@ fn build_arches(platforms: &Platforms, release: &Release, config: &Config) -> Ve
                 <ul class="contents">
                     {contents}
                 </ul>
+                {target_link}
             </li>
         ));
     }
@@ -169,13 +245,11 @@ fn content_list(
     for idx in installers {
         let installer = release.artifacts.installer(idx.to_owned());
 
-        let html = match &installer.method {
-            InstallMethod::Run { file, run_hint } => run_html(*file, run_hint, release, config),
-            InstallMethod::Download { file } => {
-                let file = release.artifacts.file(*file);
-                html!(<div class="download-wrapper"><a href=&file.download_url><button class="button primary"><span>{text!("Download")}</span><span class="button-subtitle">{text!(&file.name)}</span></button></a></div>)
-            }
-        };
+        let html = installer_html(installer, release, config);
+
+        // Explain the installer if we can
+        let description = (!installer.description.is_empty())
+            .then(|| html!(<p class="installer-description">{text!(&installer.description)}</p>));
 
         // If there's only one platform, auto-show
         let classes = if simple_platforms && is_first {
@@ -187,6 +261,7 @@ fn content_list(
         let html = html!(
             <li data-id=string_idx data-triple=target class=classes>
                 {html}
+                {description}
             </li>
         );
 
@@ -197,6 +272,38 @@ fn content_list(
     list
 }
 
+/// Get the html for using an installer, whatever its method
+fn installer_html(installer: &Installer, release: &Release, config: &Config) -> Box<div<String>> {
+    match &installer.method {
+        InstallMethod::Run { file, run_hint } => run_html(*file, run_hint, release, config),
+        InstallMethod::Download { file } => {
+            let file = release.artifacts.file(*file);
+            let details = download_details(file, config)
+                .map(|details| html!(<span class="button-subtitle">{text!(details)}</span>));
+            html!(<div class="download-wrapper"><a href=&file.download_url><button class="button primary"><span>{text!("Download")}</span><span class="button-subtitle">{text!(&file.name)}</span>{details}</button></a></div>)
+        }
+    }
+}
+
+/// Get a short summary of a file's size/date/downloads for a download button
+fn download_details(file: &File, config: &Config) -> Option<String> {
+    let show_download_counts = config
+        .components
+        .artifacts
+        .as_ref()
+        .map(|a| a.show_download_counts)
+        .unwrap_or(false);
+    let download_count = file
+        .download_count
+        .filter(|_| show_download_counts)
+        .map(|count| format!("{count} downloads"));
+    let details: Vec<String> = [file.formatted_size(), file.formatted_date(), download_count]
+        .into_iter()
+        .flatten()
+        .collect();
+    (!details.is_empty()).then(|| details.join(" · "))
+}
+
 /// Get the html for an InstallMethod::Run
 pub fn run_html(
     file: Option<FileIdx>,
@@ -240,19 +347,19 @@ fn selector_html(platforms: &Platforms) -> Box<select<String>> {
     let mut options = vec![];
     options.push(html!(<option disabled=true selected=true value="">{text!("")}</option>));
     for target in platforms.keys() {
-        let os_name = triple_to_display_name(target).unwrap();
-        options.push(html!(<option value=target>{text!(os_name.to_owned())}</option>));
+        let os_name = triple_to_readable_name(target);
+        options.push(html!(<option value=target>{text!(os_name)}</option>));
     }
 
     html!(
-        <select id="install-arch-select">
+        <select class="install-arch-select">
             {options}
         </select>
     )
 }
 
 /// Only grab platforms that we can actually provide downloadable files for.
-fn filter_platforms(release: &Release) -> Platforms {
+pub(crate) fn filter_platforms(release: &Release) -> Platforms {
     // First try to select platforms with downloadable artifacts
     let mut platforms = HashMap::new();
     for (target, installer) in release.artifacts.installers_by_target().iter() {
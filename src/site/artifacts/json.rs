@@ -0,0 +1,217 @@
+//! A machine-readable description of releases, written out as `/artifacts.json`
+//! (for the latest release) and `/releases/<tag>.json` (for every release).
+//!
+//! These are a public API for things like installer scripts and editor extensions,
+//! so they intentionally don't expose our internal types directly. Any breaking
+//! change to the format must bump [`SCHEMA_VERSION`][].
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use crate::config::Config;
+use crate::data::artifacts::{CompanionKind, DisplayPreference, InstallMethod, TargetTriple};
+use crate::data::{Context, Release};
+use crate::errors::*;
+use crate::site::link;
+use crate::site::page::Page;
+
+/// The version of the format described here
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// A single release
+#[derive(Debug, Serialize)]
+pub struct ReleaseJson {
+    /// The version of this format
+    pub schema_version: u32,
+    /// The tag of the release (e.g. "v0.1.0")
+    pub tag: String,
+    /// The display name of the release
+    pub name: Option<String>,
+    /// The date the release was published
+    pub date: Option<String>,
+    /// Whether this is a prerelease
+    pub prerelease: bool,
+    /// The installers for each target triple, most preferred first
+    pub targets: BTreeMap<TargetTriple, Vec<InstallerJson>>,
+    /// Every file in the release
+    pub files: Vec<FileJson>,
+}
+
+/// A way to install the release
+#[derive(Debug, Serialize)]
+pub struct InstallerJson {
+    /// A brief label for the installer (e.g. "shell", "npm", "tarball")
+    pub label: String,
+    /// A longer description of the installer
+    pub description: String,
+    /// How to use the installer
+    #[serde(flatten)]
+    pub method: InstallMethodJson,
+}
+
+/// How to use an installer
+#[derive(Debug, Serialize)]
+#[serde(tag = "method", rename_all = "snake_case")]
+pub enum InstallMethodJson {
+    /// Download this file
+    Download {
+        /// The name of the file (see `files`)
+        file: String,
+        /// The URL to download it from
+        download_url: String,
+    },
+    /// Run this line in a terminal
+    Run {
+        /// The name of the script being run, if any (see `files`)
+        file: Option<String>,
+        /// The line to run
+        run_hint: String,
+    },
+}
+
+/// A file in the release
+#[derive(Debug, Serialize)]
+pub struct FileJson {
+    /// The name of the file
+    pub name: String,
+    /// The URL to download it from
+    pub download_url: String,
+    /// Where the file was originally published, if `download_url` is a mirror
+    pub origin_url: Option<String>,
+    /// A URL on this site to view the source of the file (for scripts)
+    pub view_url: Option<String>,
+    /// The URL of a file containing the checksum of this one
+    pub checksum_url: Option<String>,
+    /// The SHA-256 of the file (hex-encoded), if we know it
+    pub sha256: Option<String>,
+    /// Signatures, SBOMs and attestations for the file
+    pub companions: Vec<CompanionJson>,
+    /// The size of the file in bytes
+    pub size: Option<u64>,
+    /// When the file was last updated
+    pub updated_at: Option<String>,
+    /// How many times the file has been downloaded
+    pub download_count: Option<u64>,
+}
+
+/// A file that vouches for another file
+#[derive(Debug, Serialize)]
+pub struct CompanionJson {
+    /// What kind of file this is
+    /// ("gpg", "minisign", "cosign", "sigstore_bundle", "sbom" or "provenance")
+    pub kind: &'static str,
+    /// The name of the file (see `files`)
+    pub file: String,
+    /// The name of the certificate for the signature (for "cosign")
+    pub certificate: Option<String>,
+}
+
+/// Build `artifacts.json` and `releases/<tag>.json` for all the releases
+pub fn pages(context: &Context, config: &Config) -> Result<Vec<Page>> {
+    let mut pages = vec![];
+    if let Some(latest) = context.latest() {
+        pages.push(page(latest, "artifacts.json", config)?);
+    }
+    for release in &context.releases {
+        let filename = format!("releases/{}.json", release.source.version_tag());
+        pages.push(page(release, &filename, config)?);
+    }
+    Ok(pages)
+}
+
+fn page(release: &Release, filename: &str, config: &Config) -> Result<Page> {
+    let json = build(release, config);
+    Ok(Page {
+        contents: serde_json::to_string_pretty(&json)?,
+        filename: filename.to_owned(),
+    })
+}
+
+/// Describe a release
+pub fn build(release: &Release, config: &Config) -> ReleaseJson {
+    let artifacts = &release.artifacts;
+
+    let targets = artifacts
+        .installers_by_target()
+        .iter()
+        .map(|(target, installers)| {
+            let installers = installers
+                .iter()
+                .map(|idx| artifacts.installer(*idx))
+                .filter(|installer| installer.display != DisplayPreference::Hidden)
+                .map(|installer| {
+                    let method = match &installer.method {
+                        InstallMethod::Download { file } => {
+                            let file = artifacts.file(*file);
+                            InstallMethodJson::Download {
+                                file: file.name.clone(),
+                                download_url: file.download_url.clone(),
+                            }
+                        }
+                        InstallMethod::Run { file, run_hint } => InstallMethodJson::Run {
+                            file: file.map(|file| artifacts.file(file).name.clone()),
+                            run_hint: run_hint.clone(),
+                        },
+                    };
+                    InstallerJson {
+                        label: installer.label.clone(),
+                        description: installer.description.clone(),
+                        method,
+                    }
+                })
+                .collect();
+            (target.clone(), installers)
+        })
+        .collect();
+
+    let files = artifacts
+        .file_indices()
+        .map(|idx| (idx, artifacts.file(idx)))
+        .map(|(idx, file)| FileJson {
+            name: file.name.clone(),
+            download_url: file.download_url.clone(),
+            origin_url: artifacts.origin_url(idx).map(|url| url.to_owned()),
+            view_url: file
+                .view_path
+                .as_ref()
+                .map(|path| link::generate(&config.build.path_prefix, path)),
+            checksum_url: file
+                .checksum_file
+                .map(|checksum| artifacts.file(checksum).download_url.clone()),
+            sha256: file.sha256.clone(),
+            companions: file
+                .companion_files
+                .iter()
+                .map(|companion| {
+                    let (kind, certificate) = match companion.kind {
+                        CompanionKind::Gpg => ("gpg", None),
+                        CompanionKind::Minisign => ("minisign", None),
+                        CompanionKind::Cosign { certificate } => ("cosign", Some(certificate)),
+                        CompanionKind::SigstoreBundle => ("sigstore_bundle", None),
+                        CompanionKind::Sbom => ("sbom", None),
+                        CompanionKind::Provenance => ("provenance", None),
+                    };
+                    CompanionJson {
+                        kind,
+                        file: artifacts.file(companion.file).name.clone(),
+                        certificate: certificate.map(|cert| artifacts.file(cert).name.clone()),
+                    }
+                })
+                .collect(),
+            size: file.size,
+            updated_at: file.updated_at.clone(),
+            download_count: file.download_count,
+        })
+        .collect();
+
+    ReleaseJson {
+        schema_version: SCHEMA_VERSION,
+        tag: release.source.version_tag().to_owned(),
+        name: release.source.name().map(|name| name.to_owned()),
+        date: release.source.date().map(|date| date.to_owned()),
+        prerelease: release.source.is_prerelease(),
+        targets,
+        files,
+    }
+}
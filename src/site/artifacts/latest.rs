@@ -0,0 +1,122 @@
+//! Stable URLs that always point at the latest release
+//!
+//! * `/download/latest/<target>` redirects to the preferred download for that target
+//! * `/install.sh` and `/install.ps1` are copies of the latest installer scripts
+
+use crate::config::Config;
+use crate::data::artifacts::{DisplayPreference, InstallMethod};
+use crate::data::cache::HttpCache;
+use crate::data::Release;
+use crate::errors::*;
+use crate::message::{Message, MessageType};
+use crate::site::page::Page;
+
+/// The stable script names we publish, and the extension of the script they're copied from
+const STABLE_SCRIPTS: &[(&str, &str)] = &[("install.sh", ".sh"), ("install.ps1", ".ps1")];
+
+/// Build the redirect pages and stable script copies for the latest release
+pub fn pages(release: &Release, config: &Config) -> Result<Vec<Page>> {
+    let mut pages = redirect_pages(release);
+    pages.extend(script_pages(release, config));
+    Ok(pages)
+}
+
+/// Build a `download/latest/<target>` redirect for every target with something to download
+fn redirect_pages(release: &Release) -> Vec<Page> {
+    let artifacts = &release.artifacts;
+    let mut pages = vec![];
+    for (target, installers) in artifacts.installers_by_target() {
+        // Redirect to the most preferred thing that can actually be downloaded
+        let download = installers
+            .iter()
+            .map(|idx| artifacts.installer(*idx))
+            .filter(|installer| installer.display != DisplayPreference::Hidden)
+            .find_map(|installer| match installer.method {
+                InstallMethod::Download { file } => Some(artifacts.file(file)),
+                InstallMethod::Run { .. } => None,
+            });
+        if let Some(file) = download {
+            pages.push(Page {
+                contents: redirect_html(&file.download_url),
+                filename: format!("download/latest/{target}.html"),
+            });
+        }
+    }
+    pages
+}
+
+/// Copy the latest installer scripts to stable paths
+///
+/// Failing to fetch a script is never fatal, we just won't publish it.
+fn script_pages(release: &Release, config: &Config) -> Vec<Page> {
+    let artifacts = &release.artifacts;
+    let cache = HttpCache::new(config.build.cache_mode);
+    let mut pages = vec![];
+    for (stable_name, ext) in STABLE_SCRIPTS {
+        // Use the script the install widget ranks highest on any platform
+        let script = artifacts
+            .installers_by_target()
+            .values()
+            .filter_map(|installers| {
+                installers.iter().enumerate().find_map(|(rank, idx)| {
+                    match artifacts.installer(*idx).method {
+                        InstallMethod::Run {
+                            file: Some(file), ..
+                        } if artifacts.file(file).name.ends_with(ext) => Some((rank, file)),
+                        _ => None,
+                    }
+                })
+            })
+            .min_by_key(|(rank, _)| *rank)
+            .map(|(_, file)| file);
+        let Some(script_idx) = script else {
+            continue;
+        };
+        let script = artifacts.file(script_idx);
+
+        let contents = tokio::runtime::Handle::current()
+            .block_on(artifacts.fetch_file_text(script_idx, &cache));
+        match contents {
+            Ok(contents) => pages.push(Page {
+                contents,
+                filename: stable_name.to_string(),
+            }),
+            Err(OrandaError::OfflineCacheMiss { .. }) => {
+                let msg = format!(
+                    "We're offline and don't have {} cached, so we can't publish it as /{stable_name}.",
+                    script.name
+                );
+                Message::new(MessageType::Warning, &msg).print();
+            }
+            Err(e) => {
+                let msg = format!(
+                    "Couldn't fetch {} ({e}), so we can't publish it as /{stable_name}.",
+                    script.name
+                );
+                Message::new(MessageType::Warning, &msg).print();
+            }
+        }
+    }
+    pages
+}
+
+/// A page that immediately sends you to the given URL
+///
+/// This is written by hand because axohtml renders `http-equiv` as `http_equiv`.
+fn redirect_html(url: &str) -> String {
+    // Make the URL safe to put in a script tag
+    let url_literal = serde_json::to_string(url)
+        .unwrap_or_default()
+        .replace("</", "<\\/");
+    let url = escape_attr(url);
+    format!(
+        r#"<!doctype html><html lang="en"><head><title>Redirecting...</title><meta charset="utf-8"/><meta http-equiv="refresh" content="0; url={url}"/><link rel="canonical" href="{url}"/><script>window.location.replace({url_literal});</script></head><body><p>Redirecting to <a href="{url}">{url}</a></p></body></html>"#
+    )
+}
+
+fn escape_attr(val: &str) -> String {
+    val.replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
@@ -1,24 +1,121 @@
 use std::collections::HashMap;
 
 use crate::config::Config;
-use crate::data::artifacts::{DisplayPreference, InstallMethod};
+use crate::data::artifacts::{DisplayPreference, InstallMethod, TargetTriple};
 use crate::data::{Context, Release};
 use crate::errors::*;
 
 mod installers;
+pub mod json;
+pub mod latest;
 mod table;
 
-use axohtml::elements::div;
-use axohtml::{html, text};
+use axohtml::elements::{code, details, div, li};
+use axohtml::{html, text, unsafe_text};
+
+use crate::site::{icons, link, markdown};
+
+/// Where the embeddable install widget goes
+pub const EMBED_PATH: &str = "embed/install/";
 
 pub fn page(context: &Context, config: &Config) -> Result<String> {
     let Some(release) = context.latest() else {
         return Ok(String::new());
     };
 
+    let downloads = if context.channels.len() > 1 {
+        channels_html(context, |release| downloads_html(release, config))?
+    } else {
+        downloads_html(release, config)?
+    };
+    let older_versions = older_versions(context, config)?;
+    let embed_snippet = embed_snippet(config);
+
+    Ok(html!(
+    <div>
+        {downloads}
+        {older_versions}
+        {embed_snippet}
+    </div>
+    )
+    .to_string())
+}
+
+/// Build the install widget on its own, for other sites to embed in an iframe
+pub fn embed(context: &Context, config: &Config) -> Result<String> {
+    let header = header(context, config)?;
+    let html: Box<div<String>> = html!(
+    <div class="embed-install">
+        {unsafe_text!(header)}
+    </div>
+    );
+    Ok(html.to_string())
+}
+
+/// Explain how to embed the install widget in another site
+fn embed_snippet(config: &Config) -> Box<div<String>> {
+    // The snippet goes on other sites, so it needs a full url if we know it
+    let path = link::generate(&config.build.path_prefix, EMBED_PATH);
+    let url = match &config.project.homepage {
+        Some(homepage) => format!("{}{path}", homepage.trim_end_matches('/')),
+        None => path,
+    };
+    let snippet = format!(
+        r#"<iframe src="{url}" title="Install {}" width="100%" height="300" style="border: none" allow="clipboard-write"></iframe>"#,
+        config.project.name
+    );
+    let code = match markdown::syntax_highlight(Some("html"), &snippet, &config.styles.syntax_theme)
+    {
+        Ok(code) => code,
+        Err(_) => {
+            // Unlike a run hint, this is HTML, so it needs escaping
+            let code: Box<code<String>> = html!(<code class="inline-code">{text!(&snippet)}</code>);
+            code.to_string()
+        }
+    };
+    let icon = icons::copy();
+
+    html!(
+    <div class="embed-snippet">
+        <h3>{text!("Embed this installer")}</h3>
+        <p>{text!("To show this installer on another site, add:")}</p>
+        <div class="install-code-wrapper">
+            {unsafe_text!(code)}
+            <button class="button copy-clipboard-button primary" data-copy=&snippet>{icon}</button>
+        </div>
+    </div>
+    )
+}
+
+/// Build a page for every platform the latest release can be installed on
+///
+/// These are plain HTML, for search engines and anyone without javascript.
+/// Returns the target triple of each page along with its contents.
+pub fn target_pages(context: &Context, config: &Config) -> Result<Vec<(TargetTriple, String)>> {
+    let Some(release) = context.latest() else {
+        return Ok(vec![]);
+    };
+    // Only the platforms the install widget links to get a page
+    let mut platforms: Vec<_> = installers::filter_platforms(release)
+        .into_iter()
+        // The universal "all" platform doesn't get its own page
+        .filter(|(target, _)| target != "all")
+        .collect();
+    platforms.sort_by(|(a, _), (b, _)| a.cmp(b));
+    let pages = platforms
+        .into_iter()
+        .map(|(target, installers)| {
+            let page = installers::build_target_page(release, &target, &installers, config);
+            (target, page.to_string())
+        })
+        .collect();
+    Ok(pages)
+}
+
+/// Build the installer scripts and downloads table for a release
+fn downloads_html(release: &Release, config: &Config) -> Result<Box<div<String>>> {
     let installer_scripts = scripts(release, config)?;
     let artifact_table = table::build(release, config)?;
-
     Ok(html!(
     <div>
         <div class="package-managers-downloads">
@@ -28,8 +125,128 @@ pub fn page(context: &Context, config: &Config) -> Result<String> {
             {artifact_table}
         </div>
     </div>
-    )
-    .to_string())
+    ))
+}
+
+/// Build something for the latest release of every release channel, and a switcher between them
+///
+/// Only the default channel is shown until another one is picked.
+fn channels_html(
+    context: &Context,
+    build: impl Fn(&Release) -> Result<Box<div<String>>>,
+) -> Result<Box<div<String>>> {
+    let default_channel = context
+        .default_channel()
+        .map(|channel| channel.name.as_str());
+    let mut options = vec![];
+    let mut channels = vec![];
+    for channel in &context.channels {
+        let name = &channel.name;
+        let is_default = default_channel == Some(name.as_str());
+        let release = &context.releases[channel.latest_release];
+        let contents = build(release)?;
+        let classes = if is_default {
+            "channel"
+        } else {
+            "channel hidden"
+        };
+        options.push(html!(<option value=name selected=is_default>{text!(name)}</option>));
+        channels.push(html!(<div class=classes data-channel=name>{contents}</div>));
+    }
+
+    Ok(html!(
+    <div class="release-channels">
+        <div class="channel-select hidden">
+            {text!("Channel: ")}
+            <select>
+                {options}
+            </select>
+        </div>
+        {channels}
+    </div>
+    ))
+}
+
+/// Build the downloads section for a release's own page
+///
+/// Returns `None` if the release doesn't have anything to install.
+pub fn release_downloads(release: &Release, config: &Config) -> Result<Option<Box<div<String>>>> {
+    if !has_downloads(release, config) {
+        return Ok(None);
+    }
+    let downloads = downloads_html(release, config)?;
+    Ok(Some(html!(
+    <div id="downloads" class="release-downloads">
+        {downloads}
+    </div>
+    )))
+}
+
+/// Build an archive of every release other than the latest, linking to their downloads
+///
+/// If the changelog is enabled, this links to the downloads on each release's page.
+/// Otherwise those pages don't exist, so the downloads are inlined in collapsed sections.
+fn older_versions(context: &Context, config: &Config) -> Result<Option<Box<div<String>>>> {
+    // Releases we already show above don't need to be listed again
+    let shown: Vec<usize> = context
+        .channels
+        .iter()
+        .map(|channel| channel.latest_release)
+        .chain(context.latest_release)
+        .collect();
+    let mut versions: Vec<Box<li<String>>> = vec![];
+    for (idx, release) in context.releases.iter().enumerate() {
+        if shown.contains(&idx) || !has_downloads(release, config) {
+            continue;
+        }
+        let tag = release.source.version_tag();
+        let date = release
+            .source
+            .formatted_date()
+            .map(|date| html!(<small class="published-date">{text!(date)}</small>));
+        let label = if release.source.is_prerelease() {
+            format!("{tag} (prerelease)")
+        } else {
+            tag.to_owned()
+        };
+        if config.components.changelog {
+            let href = link::generate(&config.build.path_prefix, &format!("changelog/{tag}/"));
+            let href = format!("{href}#downloads");
+            versions.push(html!(<li><a href=href>{text!(label)}</a> {date}</li>));
+        } else {
+            // axohtml's `html!` can't build a `<details>`, so we assemble it by hand
+            let mut details = details::new(html!(<summary>{text!(label)} {date}</summary>));
+            details.children.push(table::build(release, config)?);
+            versions.push(html!(<li>{Box::new(details)}</li>));
+        }
+    }
+
+    if versions.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(html!(
+    <div class="older-versions">
+        <h3>{text!("Older Versions")}</h3>
+        <ul>
+            {versions}
+        </ul>
+    </div>
+    )))
+}
+
+/// Whether a release has anything we'd show on an install page
+fn has_downloads(release: &Release, config: &Config) -> bool {
+    let artifacts_enabled = config
+        .components
+        .artifacts
+        .as_ref()
+        .map(|a| a.has_some())
+        .unwrap_or(false);
+    artifacts_enabled
+        && release
+            .artifacts
+            .installers()
+            .any(|(_, installer)| installer.display != DisplayPreference::Hidden)
 }
 
 pub fn scripts(release: &Release, config: &Config) -> Result<Vec<Box<div<String>>>> {
@@ -56,9 +273,12 @@ pub fn scripts(release: &Release, config: &Config) -> Result<Vec<Box<div<String>
             continue;
         };
         let script = installers::run_html(*file, run_hint, release, config);
+        let description = (!installer.description.is_empty())
+            .then(|| html!(<p class="installer-description">{text!(&installer.description)}</p>));
         output.push(html!(
         <div>
             <h3>{text!(label)}</h3>
+            {description}
             {script}
         </div>
         ));
@@ -71,6 +291,16 @@ pub fn header(context: &Context, config: &Config) -> Result<String> {
         return Ok(String::new());
     };
 
-    let header = installers::build_header(release, config)?;
+    // Only the latest release has per-platform pages
+    let latest_tag = release.source.version_tag();
+    let build_header = |release: &Release| {
+        let link_targets = release.source.version_tag() == latest_tag;
+        installers::build_header(release, config, link_targets)
+    };
+    if context.channels.len() > 1 {
+        let header = channels_html(context, build_header)?;
+        return Ok(header.to_string());
+    }
+    let header = build_header(release)?;
     Ok(header.to_string())
 }
@@ -4,13 +4,16 @@ use axohtml::elements::{div, tr};
 use axohtml::{html, text};
 
 use crate::config::Config;
-use crate::data::artifacts::inference::triple_to_display_name;
-use crate::data::artifacts::InstallMethod;
+use crate::data::artifacts::inference::triple_to_readable_name;
+use crate::data::artifacts::{CompanionKind, File, InstallMethod, TargetTriple};
+use crate::data::github::GithubRepo;
 use crate::data::Release;
 use crate::errors::*;
 
+use super::installers;
+
 /// Build a downloads table for this release
-pub fn build(release: &Release, _config: &Config) -> Result<Box<div<String>>> {
+pub fn build(release: &Release, config: &Config) -> Result<Box<div<String>>> {
     let mut table = vec![];
 
     // We only display files that were detected to be a downloadable archive
@@ -24,11 +27,7 @@ pub fn build(release: &Release, _config: &Config) -> Result<Box<div<String>>> {
             file,
             (
                 release.artifacts.file(file),
-                installer
-                    .targets
-                    .keys()
-                    .map(|s| triple_to_display_name(s))
-                    .collect::<Vec<_>>(),
+                installer.targets.keys().collect::<Vec<_>>(),
             ),
         );
     }
@@ -39,70 +38,250 @@ pub fn build(release: &Release, _config: &Config) -> Result<Box<div<String>>> {
         return Ok(html!(<div><h3>{text!("No Downloads")}</h3></div>));
     }
 
-    // If any files have checksums, add a column for that
+    // If any files have checksums or signatures, add columns for those
     let has_checksum_files = files.iter().any(|(_, (f, _))| f.checksum_file.is_some());
+    let has_companion_files = files
+        .iter()
+        .any(|(_, (f, _))| !f.companion_files.is_empty());
+    // Same for the rest of the metadata we might know about files
+    let has_sizes = files.iter().any(|(_, (f, _))| f.size.is_some());
+    let has_dates = files.iter().any(|(_, (f, _))| f.updated_at.is_some());
+    let show_download_counts = config
+        .components
+        .artifacts
+        .as_ref()
+        .map(|a| a.show_download_counts)
+        .unwrap_or(false)
+        && files.iter().any(|(_, (f, _))| f.download_count.is_some());
 
     // Add the headings
     {
         let mut row = vec![];
         row.push(html!(<th>{text!("File")}</th>));
         row.push(html!(<th>{text!("Platform")}</th>));
+        if has_sizes {
+            row.push(html!(<th>{text!("Size")}</th>));
+        }
+        if has_dates {
+            row.push(html!(<th>{text!("Date")}</th>));
+        }
+        if show_download_counts {
+            row.push(html!(<th>{text!("Downloads")}</th>));
+        }
         if has_checksum_files {
             row.push(html!(<th>{text!("Checksum")}</th>));
         }
+        if has_companion_files {
+            row.push(html!(<th>{text!("Signatures")}</th>));
+        }
         table.push(html!(<tr>{row}</tr>));
     }
 
     // Now add the rows
-    for (_, (file, platforms)) in files {
+    let repo = config
+        .project
+        .repository
+        .as_deref()
+        .and_then(|url| GithubRepo::from_url(url).ok());
+    let show_origin = config
+        .components
+        .artifacts
+        .as_ref()
+        .and_then(|a| a.mirror.as_ref())
+        .map(|mirror| mirror.show_origin)
+        .unwrap_or(false);
+    let mut verify = vec![];
+    for (file_idx, (file, targets)) in files {
         let mut row = vec![];
 
-        // Link the file
+        // Link the file (and optionally where it came from, if it's on a mirror)
         let url = &file.download_url;
         let name = &file.name;
-        row.push(html!(<td><a href=url>{text!(name)}</a></td>));
+        let origin = release
+            .artifacts
+            .origin_url(file_idx)
+            .filter(|_| show_origin)
+            .map(|origin| html!(<a class="origin-link" href=origin>{text!("origin")}</a>));
+        row.push(html!(<td><a href=url>{text!(name)}</a> {origin}</td>));
 
         // List platforms
         let mut platform_list = String::new();
         let mut multi_platform = false;
+        let platforms = targets.iter().map(|s| triple_to_readable_name(s));
         for platform in platforms {
-            let Some(platform) = platform else {
-                continue;
-            };
             if multi_platform {
                 platform_list.push_str(", ");
             }
-            platform_list.push_str(platform);
+            platform_list.push_str(&platform);
             multi_platform = true;
         }
         row.push(html!(<td>{text!(platform_list)}</td>));
 
+        // Optionally include other metadata
+        if has_sizes {
+            let size = file.formatted_size().unwrap_or_default();
+            row.push(html!(<td>{text!(size)}</td>));
+        }
+        if has_dates {
+            let date = file.formatted_date().unwrap_or_default();
+            row.push(html!(<td>{text!(date)}</td>));
+        }
+        if show_download_counts {
+            let count = file
+                .download_count
+                .map(|count| count.to_string())
+                .unwrap_or_default();
+            row.push(html!(<td>{text!(count)}</td>));
+        }
+
         // Optionally include checksums
         if has_checksum_files {
-            let checksum_entry = if let Some(checksum) = file.checksum_file {
-                let checksum_url = &release.artifacts.file(checksum).download_url;
-                html!(<td><a href=checksum_url>{text!("checksum")}</a></td>)
-            } else {
-                html!(<td></td>)
+            let checksum_entry = match (file.checksum_file, &file.sha256) {
+                (Some(checksum), Some(sha256)) => {
+                    let checksum_url = &release.artifacts.file(checksum).download_url;
+                    html!(<td><code class="checksum">{text!(sha256)}</code> <a href=checksum_url>{text!("checksum")}</a></td>)
+                }
+                (Some(checksum), None) => {
+                    let checksum_url = &release.artifacts.file(checksum).download_url;
+                    html!(<td><a href=checksum_url>{text!("checksum")}</a></td>)
+                }
+                (None, _) => html!(<td></td>),
             };
             row.push(checksum_entry);
         }
+
+        // Optionally include signatures/SBOMs/attestations
+        if has_companion_files {
+            let links: Vec<_> = file
+                .companion_files
+                .iter()
+                .map(|companion| {
+                    let url = &release.artifacts.file(companion.file).download_url;
+                    let label = companion.kind.label();
+                    html!(<li><a href=url>{text!(label)}</a></li>)
+                })
+                .collect();
+            row.push(html!(<td><ul class="companions">{links}</ul></td>));
+        }
+
+        if let Some(commands) = verify_html(file, &targets, repo.as_ref(), release, config) {
+            verify.push(commands);
+        }
         table.push(html!(<tr>{row}</tr>));
     }
 
-    Ok(html(table))
+    Ok(html(table, verify))
+}
+
+/// Build copyable commands to check a file's checksum and signatures
+fn verify_html(
+    file: &File,
+    targets: &[&TargetTriple],
+    repo: Option<&GithubRepo>,
+    release: &Release,
+    config: &Config,
+) -> Option<Box<div<String>>> {
+    let name = &file.name;
+    let mut commands = vec![];
+
+    // Checksums get a command for each platform the file is for
+    if let Some(sha256) = &file.sha256 {
+        let is_windows = |t: &str| t.contains("windows");
+        let is_mac = |t: &str| t.contains("apple");
+        let for_all = targets.is_empty() || targets.iter().any(|t| t.as_str() == "all");
+        let windows = for_all || targets.iter().any(|t| is_windows(t));
+        let mac = for_all || targets.iter().any(|t| is_mac(t));
+        let unix = for_all || targets.iter().any(|t| !is_windows(t) && !is_mac(t));
+
+        if unix {
+            let hint = format!("echo \"{sha256}  {name}\" | sha256sum -c");
+            commands.push(("SHA-256 (Linux)", hint));
+        }
+        if mac {
+            let hint = format!("echo \"{sha256}  {name}\" | shasum -a 256 -c");
+            commands.push(("SHA-256 (macOS)", hint));
+        }
+        if windows {
+            let hint = format!("(Get-FileHash {name} -Algorithm SHA256).Hash -eq \"{sha256}\"");
+            commands.push(("SHA-256 (Windows)", hint));
+        }
+    }
+
+    // Sigstore certificates are only trusted if they were issued to this project's CI
+    let identity = if let Some(repo) = repo {
+        format!(
+            "--certificate-identity-regexp \"^https://github.com/{}/{}/\" --certificate-oidc-issuer https://token.actions.githubusercontent.com",
+            repo.owner, repo.name
+        )
+    } else {
+        "--certificate-identity <identity> --certificate-oidc-issuer <issuer>".to_owned()
+    };
+    let source = if let Some(repo) = repo {
+        format!("github.com/{}/{}", repo.owner, repo.name)
+    } else {
+        "<repository>".to_owned()
+    };
+    for companion in &file.companion_files {
+        let companion_name = &release.artifacts.file(companion.file).name;
+        let hint = match companion.kind {
+            CompanionKind::Gpg => format!("gpg --verify {companion_name} {name}"),
+            CompanionKind::Minisign => format!("minisign -Vm {name} -x {companion_name}"),
+            CompanionKind::Cosign { certificate } => {
+                let certificate_name = &release.artifacts.file(certificate).name;
+                format!("cosign verify-blob --certificate {certificate_name} --signature {companion_name} {identity} {name}")
+            }
+            CompanionKind::SigstoreBundle => {
+                format!("cosign verify-blob --bundle {companion_name} {identity} {name}")
+            }
+            CompanionKind::Provenance => format!(
+                "slsa-verifier verify-artifact {name} --provenance-path {companion_name} --source-uri {source}"
+            ),
+            // There's nothing to run for an SBOM, it's just linked in the table
+            CompanionKind::Sbom => continue,
+        };
+        commands.push((companion.kind.label(), hint));
+    }
+
+    if commands.is_empty() {
+        return None;
+    }
+    let commands: Vec<_> = commands
+        .into_iter()
+        .map(|(label, hint)| {
+            let code = installers::run_html(None, &hint, release, config);
+            html!(<div><span>{text!(label)}</span>{code}</div>)
+        })
+        .collect();
+    Some(html!(
+    <div class="checksum-verify">
+        <h4>{text!(name)}</h4>
+        {commands}
+    </div>
+    ))
 }
 
 // False positive duplicate allocation warning
 // https://github.com/rust-lang/rust-clippy/issues?q=is%3Aissue+redundant_allocation+sort%3Aupdated-desc
 #[allow(clippy::vec_box)]
-fn html(table: Vec<Box<tr<String>>>) -> Box<div<String>> {
+fn html(table: Vec<Box<tr<String>>>, verify: Vec<Box<div<String>>>) -> Box<div<String>> {
+    let verify = if verify.is_empty() {
+        None
+    } else {
+        Some(html!(
+        <div>
+            <h3>{text!("Verifying Downloads")}</h3>
+            <p>{text!("After downloading a file, you can check that it wasn't corrupted or tampered with by downloading any signatures it has alongside it, and running one of these commands in the same directory.")}</p>
+            {verify}
+        </div>
+        ))
+    };
     html!(
     <div>
         <h3>{text!("Downloads")}</h3>
         <table>
             {table}
         </table>
+        {verify}
     </div>
     )
 }
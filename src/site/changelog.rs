@@ -5,7 +5,7 @@ use axohtml::{text, unsafe_text};
 use crate::config::Config;
 use crate::data::{Context, Release};
 use crate::errors::*;
-use crate::site::{icons, markdown};
+use crate::site::{artifacts, icons, markdown};
 
 pub fn build(context: &Context, config: &Config) -> Result<String> {
     let mut releases_html: Vec<Box<section<String>>> = vec![];
@@ -61,6 +61,7 @@ pub fn build_all(context: &Context, config: &Config) -> Result<Vec<(String, Stri
 /// Builds a single, standalone release page.
 pub fn build_single_release(config: &Config, release: &Release) -> Result<String> {
     let preview = build_page_preview(release, config, false);
+    let downloads = artifacts::release_downloads(release, config)?;
     let title = release
         .source
         .name()
@@ -72,6 +73,7 @@ pub fn build_single_release(config: &Config, release: &Release) -> Result<String
             <div class="releases-body">
                 {preview}
             </div>
+            {downloads}
         </div>
     )
     .to_string())
@@ -1,5 +1,6 @@
 use std::env;
 
+use crate::data::cache::{CacheMode, HttpCache};
 use crate::errors::*;
 use crate::message::{Message, MessageType};
 
@@ -9,6 +10,11 @@ use axohtml::html;
 use camino::Utf8Path;
 use minifier::css;
 
+/// A build of oranda's CSS embedded at compile time (see build.rs), for offline builds
+///
+/// This is empty if the CSS wasn't built when oranda was compiled.
+const EMBEDDED_ORANDA_CSS: &str = include_str!(concat!(env!("OUT_DIR"), "/oranda.css"));
+
 fn concat_minify(css_files: &[String]) -> Result<String> {
     let mut css = String::new();
     for file in css_files {
@@ -24,18 +30,18 @@ fn concat_minify(css_files: &[String]) -> Result<String> {
     Ok(css)
 }
 
-pub fn build_oranda(
+/// Put oranda's CSS in the dist dir, and get the url to link to it with
+pub fn build_oranda_href(
     dist_dir: &str,
     path_prefix: &Option<String>,
     release_tag: &str,
-) -> Result<Box<link<String>>> {
-    let dist_dir = dist_dir;
-    let filename = fetch_css(dist_dir, release_tag)?;
-    let abs_path = crate::site::link::generate(path_prefix, &filename);
-    Ok(html!(<link rel="stylesheet" href=abs_path></link>))
+    cache_mode: CacheMode,
+) -> Result<String> {
+    let filename = fetch_css(dist_dir, release_tag, cache_mode)?;
+    Ok(crate::site::link::generate(path_prefix, &filename))
 }
 
-fn fetch_css(dist_dir: &str, release_tag: &str) -> Result<String> {
+fn fetch_css(dist_dir: &str, release_tag: &str, cache_mode: CacheMode) -> Result<String> {
     match env::var("ORANDA_CSS") {
         Ok(path) => {
             let filename = "oranda.css".to_string();
@@ -48,20 +54,36 @@ fn fetch_css(dist_dir: &str, release_tag: &str) -> Result<String> {
             let filename = format!("oranda-{release_tag}.css");
             let dest_path = Utf8Path::new(dist_dir).join(&filename);
             let oranda_css_response =
-                tokio::runtime::Handle::current().block_on(fetch_oranda(release_tag))?;
-            axoasset::LocalAsset::write_new(&oranda_css_response, dest_path)?;
-            Ok(filename)
+                tokio::runtime::Handle::current().block_on(fetch_oranda(release_tag, cache_mode));
+            match oranda_css_response {
+                Ok(oranda_css) => {
+                    axoasset::LocalAsset::write_new(&oranda_css, dest_path)?;
+                    Ok(filename)
+                }
+                Err(OrandaError::OfflineCacheMiss { .. }) => write_embedded(dist_dir, release_tag),
+                Err(e) => Err(e),
+            }
         }
     }
 }
 
-async fn fetch_oranda(release_tag: &str) -> Result<String> {
+async fn fetch_oranda(release_tag: &str, cache_mode: CacheMode) -> Result<String> {
     let oranda_css_request =
         octolotl::request::ReleaseAsset::new("axodotdev", "oranda", release_tag, "oranda.css");
-    Ok(octolotl::Request::send(&oranda_css_request, true)
-        .await?
-        .text()
-        .await?)
+    HttpCache::new(cache_mode)
+        .fetch_github(&oranda_css_request)
+        .await
+}
+
+fn write_embedded(dist_dir: &str, release_tag: &str) -> Result<String> {
+    if EMBEDDED_ORANDA_CSS.is_empty() {
+        return Err(OrandaError::OfflineNoCss);
+    }
+    let msg = format!("We're offline and don't have oranda's CSS for {release_tag} cached, using the version built into oranda instead.");
+    Message::new(MessageType::Warning, &msg).print();
+    let filename = "oranda.css".to_string();
+    LocalAsset::write_new(EMBEDDED_ORANDA_CSS, Utf8Path::new(dist_dir).join(&filename))?;
+    Ok(filename)
 }
 
 pub fn build_additional(path_prefix: &Option<String>) -> Box<link<String>> {
@@ -1,7 +1,7 @@
 use crate::config::{ArtifactsConfig, Config, FundingConfig, MdBookConfig};
 use crate::errors::*;
 use crate::message::{Message, MessageType};
-use crate::site::{link, page};
+use crate::site::{link, page, skip_offline_asset};
 
 use axoasset::Asset;
 use axohtml::elements::{div, header, img, li, nav};
@@ -119,7 +119,12 @@ fn nav(
 }
 
 pub fn create(config: &Config) -> Result<Box<header<String>>> {
-    let logo = if let Some(logo) = config.styles.logo.clone() {
+    let logo = if let Some(logo) = config
+        .styles
+        .logo
+        .clone()
+        .filter(|logo| !skip_offline_asset(config, logo, "logo"))
+    {
         Some(get_logo(logo, config)?)
     } else {
         None
@@ -14,10 +14,17 @@ let options = {
     linux64: "x86_64-unknown-linux",
     linux32: "i686-unknown-linux",
     linuxArm: "aarch64-unknown-linux",
+    linuxArmv7: "armv7-unknown-linux",
+    linuxRiscv: "riscv64gc-unknown-linux",
+
+    android: "aarch64-linux-android",
+
+    freebsd: "x86_64-unknown-freebsd",
+    freebsdArm: "aarch64-unknown-freebsd",
+    netbsd: "x86_64-unknown-netbsd",
+    illumos: "x86_64-unknown-illumos",
 
     // ios: "ios",
-    // android: "linux-android",
-    // freebsd: "freebsd",
 };
 
 function isAppleSilicon() {
@@ -95,10 +102,35 @@ function getOS() {
         }
     }
 
-    // linux
+    // linux (navigator.platform is usually something like "Linux x86_64")
     if (platform.includes("Linux")) {
         OS = options.linux64;
-        // FIXME: Can we find out whether linux 32-bit or ARM are used?
+        if (platform.includes("aarch64") || platform.includes("armv8")) {
+            OS = options.linuxArm;
+        } else if (platform.includes("armv7")) {
+            OS = options.linuxArmv7;
+        } else if (platform.includes("riscv64")) {
+            OS = options.linuxRiscv;
+        } else if (platform.includes("i686") || platform.includes("i386")) {
+            OS = options.linux32;
+        }
+    }
+
+    // android also reports a Linux platform, so this has to come after
+    if (userAgent.includes("Android")) {
+        OS = options.android;
+    }
+
+    // the BSDs and illumos
+    const lowerPlatform = platform.toLowerCase();
+    if (lowerPlatform.includes("freebsd")) {
+        OS = lowerPlatform.includes("aarch64") || lowerPlatform.includes("arm64")
+            ? options.freebsdArm
+            : options.freebsd;
+    } else if (lowerPlatform.includes("netbsd")) {
+        OS = options.netbsd;
+    } else if (lowerPlatform.includes("sunos") || userAgent.includes("SunOS")) {
+        OS = options.illumos;
     }
 
     // if (
@@ -108,9 +140,6 @@ function getOS() {
     // ) {
     //     OS = options.ios;
     // }
-    // if (platform.toLocaleLowerCase().includes("freebsd")) {
-    //     OS = options.freebsd;
-    // }
 
     return OS;
 }
@@ -118,27 +147,69 @@ function getOS() {
 let os = getOS();
 window.os = os;
 
-// Unhide and hydrate selector with events
-const archSelect = document.querySelector(".arch-select");
-if (archSelect) {
-    archSelect.classList.remove("hidden");
-    const selector = document.querySelector("#install-arch-select");
-    if (selector) {
-        selector.addEventListener("change", onArchChange);
+// Hydrate every installer widget (there's one for each release channel)
+Array.from(document.querySelectorAll(".artifacts")).forEach(initArtifacts);
+// Channel switchers without an installer widget (like the downloads on the install page) need hydrating too
+Array.from(document.querySelectorAll(".release-channels"))
+    .filter((channels) => !channels.querySelector(".artifacts"))
+    .forEach(initChannels);
+
+function initArtifacts(root) {
+    // Hydrate the release channel switcher this widget is part of, if any
+    const channels = root.closest(".release-channels");
+    if (channels) {
+        initChannels(channels);
     }
-}
 
-// Hydrate tab buttons with events
-Array.from(document.querySelectorAll(".install-tab[data-id]")).forEach((tab) => {
-    tab.addEventListener("click", onTabClick);
-});
+    // Unhide and hydrate selector with events
+    const archSelect = root.querySelector(".arch-select");
+    if (archSelect) {
+        archSelect.classList.remove("hidden");
+        const selector = root.querySelector(".install-arch-select");
+        if (selector) {
+            selector.addEventListener("change", (evt) => onArchChange(root, evt));
+        }
+    }
+
+    // Hydrate tab buttons with events
+    Array.from(root.querySelectorAll(".install-tab[data-id]")).forEach((tab) => {
+        tab.addEventListener("click", (evt) => onTabClick(root, evt));
+    });
+
+    let hit = Array.from(root.querySelectorAll(`.arch[data-arch]`)).find(
+        (a) => a.attributes["data-arch"].value.includes(os)
+    );
 
-function onArchChange(evt) {
+    if (hit) {
+        hit.classList.remove("hidden");
+        const selectEl = root.querySelector(".install-arch-select");
+        if (selectEl) {
+            selectEl.value = hit.dataset.arch;
+        }
+        const firstContentChild = hit.querySelector(".install-content:first-of-type");
+        const firstTabChild = hit.querySelector(".install-tab:first-of-type");
+        firstContentChild.classList.remove("hidden");
+        if (firstTabChild) {
+            firstTabChild.classList.add("selected");
+        }
+    } else {
+        const noDetectEl = root.querySelector(".no-autodetect");
+        if (noDetectEl) {
+            const noDetectElDetails = root.querySelector(".no-autodetect-details");
+            if (noDetectElDetails) {
+                noDetectElDetails.innerHTML = `We detected you're on ${os} but there don't seem to be installers for that. `
+            }
+            noDetectEl.classList.remove("hidden");
+        }
+    }
+}
+
+function onArchChange(root, evt) {
     // Get target
     const target = evt.currentTarget.value;
     // Find corresponding installer lists
-    const newContentEl = document.querySelector(`.arch[data-arch=${target}]`);
-    const oldContentEl = document.querySelector(`.arch[data-arch]:not(.hidden)`);
+    const newContentEl = root.querySelector(`.arch[data-arch=${target}]`);
+    const oldContentEl = root.querySelector(`.arch[data-arch]:not(.hidden)`);
     // Hide old content element (if applicable)
     if (oldContentEl) {
         oldContentEl.classList.add("hidden");
@@ -155,19 +226,19 @@ function onArchChange(evt) {
         }
     }
     // Hide "no OS detected" message
-    const noDetectEl = document.querySelector(".no-autodetect");
+    const noDetectEl = root.querySelector(".no-autodetect");
     noDetectEl.classList.add("hidden");
 }
 
-function onTabClick(evt) {
+function onTabClick(root, evt) {
     // Get target and ID
     const {triple, id} = evt.currentTarget.dataset;
     if (triple) {
         // Find corresponding content elements
-        const newContentEl = document.querySelector(`.install-content[data-id="${String(id)}"][data-triple=${triple}]`);
-        const oldContentEl = document.querySelector(`.install-content[data-triple=${triple}][data-id]:not(.hidden)`);
+        const newContentEl = root.querySelector(`.install-content[data-id="${String(id)}"][data-triple=${triple}]`);
+        const oldContentEl = root.querySelector(`.install-content[data-triple=${triple}][data-id]:not(.hidden)`);
         // Find old tab to unselect
-        const oldTabEl = document.querySelector(`.install-tab[data-triple=${triple}].selected`);
+        const oldTabEl = root.querySelector(`.install-tab[data-triple=${triple}].selected`);
         // Hide old content element
         if (oldContentEl && oldTabEl) {
             oldContentEl.classList.add("hidden");
@@ -181,29 +252,20 @@ function onTabClick(evt) {
     }
 }
 
-let hit = Array.from(document.querySelectorAll(`.arch[data-arch]`)).find(
-    (a) => a.attributes["data-arch"].value.includes(os)
-);
-
-if (hit) {
-    hit.classList.remove("hidden");
-    const selectEl = document.querySelector("#install-arch-select");
-    selectEl.value = hit.dataset.arch;
-    const firstContentChild = hit.querySelector(".install-content:first-of-type");
-    const firstTabChild = hit.querySelector(".install-tab:first-of-type");
-    firstContentChild.classList.remove("hidden");
-    if (firstTabChild) {
-        firstTabChild.classList.add("selected");
-    }
-} else {
-    const noDetectEl = document.querySelector(".no-autodetect");
-    if (noDetectEl) {
-        const noDetectElDetails = document.querySelector(".no-autodetect-details");
-        if (noDetectElDetails) {
-            noDetectElDetails.innerHTML = `We detected you're on ${os} but there don't seem to be installers for that. `
-        }
-        noDetectEl.classList.remove("hidden");
+function initChannels(channels) {
+    // Every channel has a widget, but the switcher only needs hydrating once
+    const channelSelect = channels.querySelector(":scope > .channel-select.hidden");
+    if (!channelSelect) {
+        return;
     }
+    // Unhide and hydrate the switcher, which only switches its own channels
+    channelSelect.classList.remove("hidden");
+    channelSelect.querySelector("select").addEventListener("change", (evt) => {
+        const channel = evt.currentTarget.value;
+        Array.from(channels.querySelectorAll(":scope > .channel[data-channel]")).forEach((el) => {
+            el.classList.toggle("hidden", el.dataset.channel !== channel);
+        });
+    });
 }
 
 let copyButtons = Array.from(document.querySelectorAll("[data-copy]"));
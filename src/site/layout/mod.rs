@@ -3,6 +3,7 @@ use axohtml::{html, text};
 use crate::config::Config;
 use crate::errors::*;
 use crate::site::oranda_theme::OrandaTheme;
+use crate::site::skip_offline_asset;
 
 pub mod css;
 mod footer;
@@ -14,6 +15,8 @@ use javascript::analytics::Analytics;
 #[derive(Debug)]
 pub struct Layout {
     template: String,
+    /// A bare version of the template, for pages that other sites embed in an iframe
+    embed_template: String,
 }
 
 const DOCTYPE: &str = r#"<!doctype html>"#;
@@ -22,7 +25,16 @@ const OS_SCRIPT_PLACEHOLDER: &str = "{{{OS_SCRIPT}}}";
 
 impl Layout {
     pub fn render(&self, body: String, os_script: Option<String>) -> String {
-        self.template
+        Self::fill(&self.template, body, os_script)
+    }
+
+    /// Render a page without the site's header and footer, to be embedded in other sites
+    pub fn render_embed(&self, body: String, os_script: Option<String>) -> String {
+        Self::fill(&self.embed_template, body, os_script)
+    }
+
+    fn fill(template: &str, body: String, os_script: Option<String>) -> String {
+        template
             .replace(BODY_PLACEHOLDER, &body)
             .replace(OS_SCRIPT_PLACEHOLDER, &os_script.unwrap_or(String::new()))
     }
@@ -38,7 +50,12 @@ impl Layout {
         });
         let banner = header::repo_banner(config);
         let meta_tags = head::create_meta_tags(config);
-        let favicon = if let Some(favicon) = config.styles.favicon.clone() {
+        let favicon = if let Some(favicon) = config
+            .styles
+            .favicon
+            .clone()
+            .filter(|favicon| !skip_offline_asset(config, favicon, "favicon"))
+        {
             Some(head::get_favicon(
                 favicon,
                 config.build.dist_dir.clone(),
@@ -49,15 +66,14 @@ impl Layout {
         };
         let footer = footer::create_footer(config);
 
-        let additional_css = if !config.styles.additional_css.is_empty() {
-            Some(css::build_additional(&config.build.path_prefix))
-        } else {
-            None
-        };
-        let oranda_css = css::build_oranda(
+        let has_additional_css = !config.styles.additional_css.is_empty();
+        let additional_css =
+            || has_additional_css.then(|| css::build_additional(&config.build.path_prefix));
+        let oranda_css_href = css::build_oranda_href(
             &config.build.dist_dir,
             &config.build.path_prefix,
             &config.styles.oranda_css_version,
+            config.build.cache_mode,
         )?;
         let analytics = Analytics::new(&config.marketing.analytics);
         let template_html: String = html!(
@@ -67,8 +83,8 @@ impl Layout {
                 {homepage}
                 {favicon}
                 {meta_tags}
-                {oranda_css}
-                {additional_css}
+                <link rel="stylesheet" href=&oranda_css_href></link>
+                {additional_css()}
             </head>
             <body>
             <div class="container">
@@ -91,6 +107,27 @@ impl Layout {
 
         let template = format!("{DOCTYPE}{template_html}");
 
-        Ok(Layout { template })
+        // Links in embedded pages should leave the embedding site alone
+        let embed_html: String = html!(
+        <html lang="en" id="oranda" class=theme>
+            <head>
+                <title>{ text!(name) }</title>
+                <base target="_blank" />
+                <link rel="stylesheet" href=&oranda_css_href></link>
+                {additional_css()}
+            </head>
+            <body class="embed">
+                <div>{text!(BODY_PLACEHOLDER)}</div>
+                <div>{text!(OS_SCRIPT_PLACEHOLDER)}</div>
+            </body>
+        </html>
+        )
+        .to_string();
+        let embed_template = format!("{DOCTYPE}{embed_html}");
+
+        Ok(Layout {
+            template,
+            embed_template,
+        })
     }
 }
@@ -5,7 +5,7 @@ use camino::{Utf8Path, Utf8PathBuf};
 use indexmap::IndexMap;
 
 use crate::config::Config;
-use crate::data::{funding::Funding, Context};
+use crate::data::{artifacts::LocalFile, funding::Funding, Context};
 use crate::errors::*;
 use crate::message::{Message, MessageType};
 
@@ -27,6 +27,8 @@ pub mod page;
 #[derive(Debug)]
 pub struct Site {
     pages: Vec<Page>,
+    /// Release files that are hosted with the site
+    local_files: Vec<LocalFile>,
 }
 
 impl Site {
@@ -46,18 +48,13 @@ impl Site {
         }
 
         let mut index = None;
+        let mut local_files = vec![];
 
         if Self::needs_context(config) {
-            let mut context = match &config.project.repository {
-                Some(repo_url) => Context::new_github(
-                    repo_url,
-                    &config.project,
-                    config.components.artifacts.as_ref(),
-                )?,
-                None => {
-                    Context::new_current(&config.project, config.components.artifacts.as_ref())?
-                }
-            };
+            let mut context = Context::new(config)?;
+            for release in &context.releases {
+                local_files.extend(release.artifacts.local_files().cloned());
+            }
             // FIXME: change the config so that you can set `artifacts: false` and disable this?
             let artifacts_enabled = config
                 .components
@@ -66,11 +63,20 @@ impl Site {
                 .map(|a| a.has_some())
                 .unwrap_or(false);
             if context.latest().is_some() && artifacts_enabled {
-                context
-                    .latest_mut()
-                    .unwrap()
-                    .artifacts
-                    .make_scripts_viewable(config)?;
+                let latest_artifacts = &mut context.latest_mut().unwrap().artifacts;
+                latest_artifacts.make_scripts_viewable(config)?;
+                latest_artifacts.fetch_checksums(config)?;
+                // The latest releases of other release channels are shown too, but their scripts
+                // would have the same names as the latest release's, so we just link to those
+                let channel_releases: Vec<usize> = context
+                    .channels
+                    .iter()
+                    .map(|channel| channel.latest_release)
+                    .filter(|idx| Some(*idx) != context.latest_release)
+                    .collect();
+                for idx in channel_releases {
+                    context.releases[idx].artifacts.fetch_checksums(config)?;
+                }
                 index = Some(Page::index_with_artifacts(
                     &context,
                     &layout_template,
@@ -80,6 +86,26 @@ impl Site {
                 let artifacts_page =
                     Page::new_from_contents(body, "artifacts.html", &layout_template, config);
                 pages.push(artifacts_page);
+                for (target, body) in artifacts::target_pages(&context, config)? {
+                    let page = Page::new_from_contents(
+                        body,
+                        &format!("artifacts/{target}.html"),
+                        &layout_template,
+                        config,
+                    );
+                    pages.push(page);
+                }
+                let embed = artifacts::embed(&context, config)?;
+                pages.push(Page::new_embed(
+                    embed,
+                    &format!("{}index.html", artifacts::EMBED_PATH),
+                    &layout_template,
+                    config,
+                ));
+                pages.append(&mut artifacts::json::pages(&context, config)?);
+                if let Some(latest) = context.latest() {
+                    pages.append(&mut artifacts::latest::pages(latest, config)?);
+                }
             }
             if config.components.changelog {
                 let mut changelog_pages =
@@ -95,7 +121,7 @@ impl Site {
         }
 
         pages.push(index.unwrap_or(Page::index(&layout_template, config)?));
-        Ok(Site { pages })
+        Ok(Site { pages, local_files })
     }
 
     fn needs_context(config: &Config) -> bool {
@@ -169,7 +195,10 @@ impl Site {
             let filename_path = Utf8PathBuf::from(&page.filename);
             // Prepare to write a "pretty link" for pages that aren't index.html already. This essentially means that we rewrite
             // the page from "page.html" to "page/index.html", so that it can be loaded as "mysite.com/page" in the browser.
-            let full_path: Utf8PathBuf = if !filename_path.ends_with("index.html") {
+            // Non-HTML files (like our JSON files) are written as-is.
+            let full_path: Utf8PathBuf = if filename_path.extension() == Some("html")
+                && !filename_path.ends_with("index.html")
+            {
                 // FIXME: Can we do anything BUT unwrap here? What's the smart way to deal with a missing filename path portion?
                 let file_stem = filename_path.file_stem().unwrap();
                 let parent = filename_path.parent().unwrap_or("".into());
@@ -179,6 +208,12 @@ impl Site {
             };
             LocalAsset::write_new_all(&page.contents, full_path)?;
         }
+        for local_file in self.local_files {
+            // Local files keep their names, so we only need to know which directory they go in
+            let dest_dir = dist.join(local_file.site_path.parent().unwrap_or("".into()));
+            LocalAsset::create_dir_all(&dest_dir)?;
+            LocalAsset::copy(&local_file.path, &dest_dir)?;
+        }
         if let Some(book_cfg) = &config.components.mdbook {
             mdbook::build_mdbook(
                 &dist,
@@ -194,7 +229,12 @@ impl Site {
 
         let additional_css = &config.styles.additional_css;
         if !additional_css.is_empty() {
-            css::write_additional(additional_css, &dist)?;
+            let additional_css: Vec<String> = additional_css
+                .iter()
+                .filter(|path| !skip_offline_asset(config, path, "additional CSS file"))
+                .cloned()
+                .collect();
+            css::write_additional(&additional_css, &dist)?;
         }
 
         Ok(())
@@ -213,3 +253,17 @@ impl Site {
         }
     }
 }
+
+/// Whether a remote asset (like a logo hosted elsewhere) has to be skipped because we're offline
+///
+/// Warns about it if so.
+pub(crate) fn skip_offline_asset(config: &Config, path: &str, what: &str) -> bool {
+    let is_remote = path.starts_with("http://") || path.starts_with("https://");
+    if is_remote && config.build.cache_mode.is_offline() {
+        let msg = format!("We're offline, so your {what} at {path} will be left out.");
+        Message::new(MessageType::Warning, &msg).print();
+        true
+    } else {
+        false
+    }
+}
@@ -74,6 +74,16 @@ impl Page {
         }
     }
 
+    /// Create a page for other sites to embed, without our header and footer
+    pub fn new_embed(body: String, filename: &str, layout: &Layout, config: &Config) -> Self {
+        let os_script = javascript::build_os_script(&config.build.path_prefix);
+        let contents = layout.render_embed(body, Some(os_script));
+        Page {
+            contents,
+            filename: filename.to_string(),
+        }
+    }
+
     fn load_and_render_contents(source: &str, syntax_theme: &SyntaxTheme) -> Result<String> {
         let source = SourceFile::load_local(source)?;
         let contents = source.contents();
@@ -46,12 +46,7 @@ pub fn index(config: &Config, layout: &Layout) -> Page {
 pub fn index_with_artifacts(config: &Config, layout: &Layout) -> Page {
     reset(&config.build.dist_dir);
     let repo_url = config.project.repository.as_ref().unwrap();
-    let mut context = Context::new_github(
-        repo_url,
-        &config.project,
-        config.components.artifacts.as_ref(),
-    )
-    .unwrap();
+    let mut context = Context::new_github(repo_url, config).unwrap();
     if let Some(latest) = context.latest_mut() {
         latest.artifacts.make_scripts_viewable(config).unwrap();
     }
@@ -67,12 +62,7 @@ pub fn index_with_warning(config: &Config, layout: &Layout) -> Page {
 pub fn artifacts(config: &Config, layout: &Layout) -> Page {
     reset(&config.build.dist_dir);
     let repo_url = config.project.repository.as_ref().unwrap();
-    let context = Context::new_github(
-        repo_url,
-        &config.project,
-        config.components.artifacts.as_ref(),
-    )
-    .unwrap();
+    let context = Context::new_github(repo_url, config).unwrap();
     let artifacts_content = artifacts::page(&context, config).unwrap();
     Page::new_from_contents(artifacts_content, "artifacts.html", layout, config)
 }
@@ -80,12 +70,7 @@ pub fn artifacts(config: &Config, layout: &Layout) -> Page {
 pub fn changelog(config: &Config, layout: &Layout) -> Page {
     reset(&config.build.dist_dir);
     let repo_url = config.project.repository.as_ref().unwrap();
-    let context = Context::new_github(
-        repo_url,
-        &config.project,
-        config.components.artifacts.as_ref(),
-    )
-    .unwrap();
+    let context = Context::new_github(repo_url, config).unwrap();
     let changelog_content = changelog::build(&context, config).unwrap();
     Page::new_from_contents(changelog_content, "changelog.html", layout, config)
 }
@@ -0,0 +1,437 @@
+use assert_fs::fixture::{FileWriteStr, PathChild};
+
+use oranda::config::{ArtifactsConfig, AutoPackageManager, Config, DetectedPackage, MirrorConfig};
+use oranda::data::artifacts::inference::triple_to_readable_name;
+use oranda::data::Context;
+use oranda::site::artifacts;
+
+use super::fixtures::config::artifacts_config;
+use super::fixtures::release::{
+    add_files, axo_repo, context_with, current_release, installer_fields, release_from,
+    release_json, release_with_files, release_with_tarball, tag_release, tag_source, TARBALL_URL,
+};
+use super::fixtures::temp_dir;
+use crate::utils::tokio_utils::TEST_RUNTIME;
+
+#[test]
+fn it_formats_file_metadata() {
+    let release = release_with_tarball();
+    let file = release.artifacts.files().next().unwrap();
+    assert_eq!(file.formatted_size().as_deref(), Some("1.2 MiB"));
+    assert_eq!(file.formatted_date().as_deref(), Some("Jun  1 2023"));
+}
+
+#[test]
+fn it_links_checksum_files() {
+    let release = release_with_files(&["axo-x86_64-unknown-linux-gnu.tar.gz.sha256", "SHA256SUMS"]);
+
+    let json = release_json(&release, &Config::default());
+    assert_eq!(
+        json["files"][0]["checksum_url"],
+        "https://example.com/axo-x86_64-unknown-linux-gnu.tar.gz.sha256"
+    );
+    // Checksum files aren't installers themselves
+    let installers = json["targets"]["x86_64-unknown-linux-gnu"]
+        .as_array()
+        .unwrap();
+    assert_eq!(installers.len(), 1);
+    assert_eq!(installers[0]["file"], "axo-x86_64-unknown-linux-gnu.tar.gz");
+}
+
+#[test]
+fn it_links_signatures() {
+    let release = release_with_files(&[
+        "axo-x86_64-unknown-linux-gnu.tar.gz.minisig",
+        "axo-x86_64-unknown-linux-gnu.tar.gz.sig",
+        "axo-x86_64-unknown-linux-gnu.tar.gz.pem",
+        "axo-x86_64-unknown-linux-gnu.tar.gz.cdx.json",
+    ]);
+
+    let json = release_json(&release, &Config::default());
+    let companions = json["files"][0]["companions"].as_array().unwrap();
+    let kinds: Vec<_> = companions
+        .iter()
+        .map(|c| c["kind"].as_str().unwrap())
+        .collect();
+    assert_eq!(kinds, vec!["minisign", "cosign", "sbom"]);
+    assert_eq!(
+        companions[1]["certificate"],
+        "axo-x86_64-unknown-linux-gnu.tar.gz.pem"
+    );
+    // None of them are installers
+    let installers = json["targets"]["x86_64-unknown-linux-gnu"]
+        .as_array()
+        .unwrap();
+    assert_eq!(installers.len(), 1);
+}
+
+#[test]
+fn it_infers_platforms_from_go_style_names() {
+    let release = release_with_files(&[
+        "tool_1.2.0_linux_arm64.tar.gz",
+        "tool-darwin-arm64.zip",
+        "tool_1.2.0_windows_x86_64.zip",
+        "tool_1.2.0_source.tar.gz",
+    ]);
+
+    let targets: Vec<_> = release
+        .artifacts
+        .installers_by_target()
+        .keys()
+        .cloned()
+        .collect();
+    assert_eq!(
+        targets,
+        vec![
+            "aarch64-apple-darwin",
+            "aarch64-unknown-linux-gnu",
+            "x86_64-pc-windows-msvc",
+            "x86_64-unknown-linux-gnu",
+        ]
+    );
+}
+
+#[test]
+fn it_infers_less_common_targets() {
+    let mut release = tag_release("v0.1.0", "2023-06-01T00:00:00Z");
+    add_files(
+        &mut release,
+        &[
+            "tool_1.2.0_linux_armv7.tar.gz",
+            "tool_1.2.0_freebsd_amd64.tar.gz",
+            "tool_1.2.0_linux_riscv64.tar.gz",
+        ],
+    );
+    release.artifacts.add_inference();
+    release
+        .artifacts
+        .select_installers(&ArtifactsConfig::default());
+
+    let targets: Vec<_> = release.artifacts.installers_by_target().keys().collect();
+    assert_eq!(
+        targets,
+        vec![
+            "armv7-unknown-linux-gnueabihf",
+            "riscv64gc-unknown-linux-gnu",
+            "x86_64-unknown-freebsd",
+        ]
+    );
+}
+
+#[test]
+fn it_names_unknown_targets_readably() {
+    assert_eq!(
+        triple_to_readable_name("x86_64-unknown-freebsd"),
+        "x64 FreeBSD"
+    );
+    assert_eq!(
+        triple_to_readable_name("powerpc64le-unknown-linux-gnu"),
+        "powerpc64le Linux"
+    );
+    assert_eq!(
+        triple_to_readable_name("x86_64-unknown-redox"),
+        "x86_64-unknown-redox"
+    );
+}
+
+#[test]
+fn it_applies_file_config() {
+    let mut release = tag_release("v0.1.0", "2023-06-01T00:00:00Z");
+    add_files(&mut release, &["axo.dmg"]);
+    release.artifacts.add_inference();
+    let config = artifacts_config(
+        r#"{
+            "files": {
+                "*.dmg": {
+                    "targets": ["mac-silicon", "x86_64-apple-darwin"],
+                    "label": "universal dmg",
+                    "preference": "preferred"
+                }
+            }
+        }"#,
+    );
+    release.artifacts.apply_file_config(&config).unwrap();
+    release.artifacts.select_installers(&config);
+
+    let json = release_json(&release, &Config::default());
+    let targets: Vec<_> = json["targets"].as_object().unwrap().keys().collect();
+    assert_eq!(targets, vec!["aarch64-apple-darwin", "x86_64-apple-darwin"]);
+    let installer = &json["targets"]["aarch64-apple-darwin"][0];
+    assert_eq!(installer["label"], "universal dmg");
+}
+
+#[test]
+fn it_doesnt_guess_targets_for_unknown_files() {
+    let mut release = tag_release("v0.1.0", "2023-06-01T00:00:00Z");
+    add_files(
+        &mut release,
+        &["axo-x86_64-unknown-linux-gnu.tar.gz", "NOTES.txt"],
+    );
+    release.artifacts.add_inference();
+    let config = artifacts_config(r#"{ "files": { "*.txt": { "label": "Notes" } } }"#);
+    release.artifacts.apply_file_config(&config).unwrap();
+    release.artifacts.select_installers(&config);
+
+    // Without targets, we can't tell which platforms the notes are for, so they aren't offered on any
+    let json = release_json(&release, &Config::default());
+    let targets: Vec<_> = json["targets"].as_object().unwrap().keys().collect();
+    assert_eq!(targets, vec!["x86_64-unknown-linux-gnu"]);
+    assert_eq!(
+        installer_fields(&json, "x86_64-unknown-linux-gnu", "label"),
+        vec!["tarball"]
+    );
+}
+
+#[test]
+fn it_applies_installer_config() {
+    let mut release = tag_release("v0.1.0", "2023-06-01T00:00:00Z");
+    add_files(
+        &mut release,
+        &["axo-installer.sh", "axo-aarch64-apple-darwin.tar.gz"],
+    );
+    let config = artifacts_config(
+        r#"{
+            "package_managers": {
+                "preferred": { "homebrew": "brew install axo" }
+            },
+            "installers": {
+                "homebrew": {
+                    "label": "Homebrew",
+                    "description": "Installs axo with Homebrew",
+                    "priority": { "mac": 1 }
+                },
+                "tarball": {
+                    "priority": { "aarch64-apple-darwin": 2 }
+                }
+            }
+        }"#,
+    );
+    release.artifacts.add_package_managers(&config);
+    release.artifacts.add_inference();
+    release.artifacts.select_installers(&config);
+
+    let json = release_json(&release, &Config::default());
+    assert_eq!(
+        installer_fields(&json, "aarch64-apple-darwin", "label"),
+        vec!["Homebrew", "tarball", "shell"]
+    );
+    assert_eq!(
+        json["targets"]["aarch64-apple-darwin"][0]["description"],
+        "Installs axo with Homebrew"
+    );
+    // Without priorities, the script comes first as usual
+    assert_eq!(
+        installer_fields(&json, "x86_64-unknown-linux-gnu", "label"),
+        vec!["shell", "Homebrew"]
+    );
+}
+
+#[test]
+fn it_renders_run_hints() {
+    let mut release = tag_release("v0.1.0", "2023-06-01T00:00:00Z");
+    add_files(&mut release, &["axo-installer.sh", "axo-installer.ps1"]);
+    let config = artifacts_config(
+        r#"{
+            "run_hints": {
+                "shell": {
+                    "variants": { "wget": "wget -qO- {url} | sh" }
+                },
+                "powershell": {
+                    "template": "iwr {url} -OutFile {file}"
+                }
+            }
+        }"#,
+    );
+    release.artifacts.add_inference();
+    release.artifacts.apply_run_hints(&config);
+    release.artifacts.select_installers(&config);
+
+    let json = release_json(&release, &Config::default());
+    assert_eq!(
+        installer_fields(&json, "x86_64-unknown-linux-gnu", "run_hint"),
+        vec![
+            "curl --proto '=https' --tlsv1.2 -LsSf https://example.com/axo-installer.sh | sh",
+            "wget -qO- https://example.com/axo-installer.sh | sh",
+        ]
+    );
+    assert_eq!(
+        json["targets"]["x86_64-pc-windows-msvc"][0]["run_hint"],
+        "iwr https://example.com/axo-installer.ps1 -OutFile axo-installer.ps1"
+    );
+}
+
+#[test]
+fn it_suggests_package_manifests() {
+    let mut release = tag_release("v0.1.0", "2023-06-01T00:00:00Z");
+    add_files(
+        &mut release,
+        &[
+            "axo.rb",
+            "axo.json",
+            "latest.json",
+            "Axo.Axo.yaml",
+            "Axo.Axo.installer.yaml",
+            "Axo.Axo.locale.en-US.yaml",
+        ],
+    );
+    let config = ArtifactsConfig::default();
+    release
+        .artifacts
+        .add_package_manifests(&config, Some(&axo_repo()));
+    release.artifacts.add_inference();
+    release.artifacts.select_installers(&config);
+
+    let json = release_json(&release, &Config::default());
+    assert_eq!(
+        json["targets"]["aarch64-apple-darwin"][0]["run_hint"],
+        "brew install axodotdev/tap/axo"
+    );
+    assert_eq!(
+        installer_fields(&json, "x86_64-pc-windows-msvc", "run_hint"),
+        vec![
+            "scoop install https://example.com/axo.json",
+            "winget install --id Axo.Axo --exact",
+        ]
+    );
+    // Other JSON files are left alone
+    let latest = release
+        .artifacts
+        .file_indices()
+        .map(|idx| release.artifacts.file(idx))
+        .find(|file| file.name == "latest.json")
+        .unwrap();
+    assert!(latest.infer);
+}
+
+#[test]
+fn it_suggests_auto_package_managers() {
+    let mut artifacts_config = ArtifactsConfig::default();
+    artifacts_config.auto_package_managers.detected = vec![
+        DetectedPackage {
+            manager: AutoPackageManager::Cargo,
+            name: "axo".to_owned(),
+            binaries: vec!["axo".to_owned()],
+        },
+        DetectedPackage {
+            manager: AutoPackageManager::Npm,
+            name: "@axodotdev/axo".to_owned(),
+            binaries: vec!["axo".to_owned()],
+        },
+        DetectedPackage {
+            manager: AutoPackageManager::Npx,
+            name: "@axodotdev/axo".to_owned(),
+            binaries: vec!["axo-cli".to_owned()],
+        },
+    ];
+    // The user already tells people how to cargo install, so we shouldn't do it again
+    artifacts_config
+        .package_managers
+        .preferred
+        .insert("crates.io".to_owned(), "cargo install axo".to_owned());
+    let mut config = Config::default();
+    config.components.artifacts = Some(artifacts_config);
+    let release = release_from(tag_source("v1.2.0", "2023-06-01T00:00:00Z"), None, &config);
+
+    let json = release_json(&release, &config);
+    assert_eq!(
+        installer_fields(&json, "x86_64-unknown-linux-gnu", "run_hint"),
+        vec![
+            "cargo install axo",
+            "npm install -g @axodotdev/axo@1.2.0",
+            "npx --package @axodotdev/axo@1.2.0 axo-cli",
+        ]
+    );
+}
+
+#[test]
+fn it_hosts_artifacts_from_a_local_dir() {
+    let _guard = TEST_RUNTIME.enter();
+    let (tempdir, temppath) = temp_dir();
+    let sha256 = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+    for (name, contents) in [
+        ("axo-x86_64-unknown-linux-gnu.tar.gz", String::new()),
+        (
+            "axo-x86_64-unknown-linux-gnu.tar.gz.sha256",
+            format!("{sha256}  axo-x86_64-unknown-linux-gnu.tar.gz"),
+        ),
+    ] {
+        tempdir
+            .child("distrib")
+            .child(name)
+            .write_str(&contents)
+            .expect("failed to write artifact");
+    }
+
+    let mut config = Config::default();
+    config.build.dist_dir = temppath.join("public").to_string();
+    config.build.path_prefix = Some("axo".to_owned());
+    config.components.artifacts = Some(ArtifactsConfig {
+        local_dir: Some(temppath.join("distrib").to_string()),
+        ..Default::default()
+    });
+    let mut release = current_release("v1.0.0", None, &config);
+
+    // The files are hosted with the site (they're copied when it's written), and linked from there
+    let mut site_paths: Vec<_> = release
+        .artifacts
+        .local_files()
+        .map(|local_file| local_file.site_path.as_str())
+        .collect();
+    site_paths.sort();
+    assert_eq!(
+        site_paths,
+        vec![
+            "download/v1.0.0/axo-x86_64-unknown-linux-gnu.tar.gz",
+            "download/v1.0.0/axo-x86_64-unknown-linux-gnu.tar.gz.sha256",
+        ]
+    );
+    assert!(!temppath.join("public").exists());
+    let json = release_json(&release, &config);
+    assert_eq!(
+        json["targets"]["x86_64-unknown-linux-gnu"][0]["download_url"],
+        "/axo/download/v1.0.0/axo-x86_64-unknown-linux-gnu.tar.gz"
+    );
+
+    // Checksums are read from the local files, rather than downloaded
+    release.artifacts.fetch_checksums(&config).unwrap();
+    let json = release_json(&release, &config);
+    assert_eq!(json["files"][0]["sha256"], sha256);
+}
+
+#[test]
+fn it_downloads_from_a_mirror() {
+    let mut release = release_with_files(&["axo-installer.sh"]);
+    let artifacts_config = ArtifactsConfig {
+        mirror: Some(MirrorConfig {
+            url: "https://cdn.example.com/{owner}/{repo}/{tag}/{name}".to_owned(),
+            show_origin: true,
+        }),
+        ..Default::default()
+    };
+    let repo = axo_repo();
+    release
+        .artifacts
+        .apply_mirror(&artifacts_config, "v0.1.0", Some(&repo));
+    let mut config = Config::default();
+    config.components.artifacts = Some(artifacts_config);
+
+    let json = release_json(&release, &config);
+    let installers = &json["targets"]["x86_64-unknown-linux-gnu"];
+    assert_eq!(
+        installers[0]["run_hint"],
+        "curl --proto '=https' --tlsv1.2 -LsSf https://cdn.example.com/axodotdev/axo/v0.1.0/axo-installer.sh | sh"
+    );
+    assert_eq!(
+        installers[1]["download_url"],
+        "https://cdn.example.com/axodotdev/axo/v0.1.0/axo-x86_64-unknown-linux-gnu.tar.gz"
+    );
+    assert_eq!(json["files"][0]["origin_url"], TARBALL_URL);
+
+    // The downloads table links to both
+    let context = Context {
+        repo: Some(repo),
+        ..context_with(vec![release])
+    };
+    let page = artifacts::page(&context, &config).unwrap();
+    assert!(page.contains(&format!(r#"<a class="origin-link" href="{TARBALL_URL}">"#)));
+}
@@ -0,0 +1,62 @@
+use oranda::data::cache::{CacheMode, HttpCache};
+use oranda::errors::OrandaError;
+
+use super::fixtures::temp_dir;
+use crate::utils::http_server::{response, TestServer};
+use crate::utils::tokio_utils::TEST_RUNTIME;
+
+#[test]
+fn it_never_fetches_when_offline() {
+    let (_tempdir, temppath) = temp_dir();
+    let cache = HttpCache::with_dir(temppath, CacheMode::Offline);
+
+    let result = TEST_RUNTIME.block_on(cache.fetch_text("https://example.com/install.sh"));
+    assert!(matches!(result, Err(OrandaError::OfflineCacheMiss { .. })));
+}
+
+#[test]
+fn it_revalidates_cached_responses() {
+    let (_tempdir, temppath) = temp_dir();
+    let last_modified = "Thu, 01 Jun 2023 00:00:00 GMT";
+    let server = TestServer::serve(vec![
+        response(
+            "200 OK",
+            &[r#"etag: "abc""#, &format!("last-modified: {last_modified}")],
+            "echo hello",
+        ),
+        response("304 Not Modified", &[], ""),
+    ]);
+    let url = format!("{}/install.sh", server.url);
+    let cache = HttpCache::with_dir(temppath, CacheMode::Revalidate);
+
+    let first = TEST_RUNTIME.block_on(cache.fetch_text(&url)).unwrap();
+    let second = TEST_RUNTIME.block_on(cache.fetch_text(&url)).unwrap();
+    assert_eq!(first, "echo hello");
+    // The server said nothing changed, so we get what we cached
+    assert_eq!(second, "echo hello");
+
+    let requests = server.requests();
+    assert!(!requests[0].contains("if-none-match"));
+    assert!(requests[1].contains(r#"if-none-match: "abc""#));
+    assert!(requests[1].contains(&format!(
+        "if-modified-since: {}",
+        last_modified.to_lowercase()
+    )));
+}
+
+#[test]
+fn it_reuses_cached_responses_without_fetching() {
+    let (_tempdir, temppath) = temp_dir();
+    // The server only answers once, so any further fetch would fail
+    let server = TestServer::serve(vec![response("200 OK", &[], "echo hello")]);
+    let url = format!("{}/install.sh", server.url);
+
+    let cache = HttpCache::with_dir(temppath.clone(), CacheMode::Revalidate);
+    TEST_RUNTIME.block_on(cache.fetch_text(&url)).unwrap();
+    for mode in [CacheMode::Reuse, CacheMode::Offline] {
+        let cache = HttpCache::with_dir(temppath.clone(), mode);
+        let body = TEST_RUNTIME.block_on(cache.fetch_text(&url)).unwrap();
+        assert_eq!(body, "echo hello");
+    }
+    assert_eq!(server.requests().len(), 1);
+}
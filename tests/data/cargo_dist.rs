@@ -0,0 +1,184 @@
+use oranda::config::{ArtifactsConfig, Config};
+
+use super::fixtures::release::{installer_fields, release_from_manifest, release_json};
+
+#[test]
+fn it_previews_unreleased_cargo_dist_artifacts() {
+    let manifest = r#"{
+        "announcement_tag": "v0.2.0",
+        "releases": [{
+            "app_name": "axo",
+            "app_version": "0.2.0",
+            "artifacts": ["axo-x86_64-unknown-linux-gnu.tar.gz"]
+        }],
+        "artifacts": {
+            "axo-x86_64-unknown-linux-gnu.tar.gz": {
+                "name": "axo-x86_64-unknown-linux-gnu.tar.gz",
+                "kind": "executable-zip",
+                "target_triples": ["x86_64-unknown-linux-gnu"],
+                "checksum": "axo-x86_64-unknown-linux-gnu.tar.gz.sha256"
+            }
+        }
+    }"#;
+    let mut config = Config::default();
+    let release = release_from_manifest(manifest, &mut config);
+
+    // The files don't exist yet, but we know where they'll be once v0.2.0 is published
+    let json = release_json(&release, &config);
+    let installer = &json["targets"]["x86_64-unknown-linux-gnu"][0];
+    assert_eq!(installer["label"], "tarball");
+    assert_eq!(
+        installer["download_url"],
+        "https://github.com/axodotdev/axo/releases/download/v0.2.0/axo-x86_64-unknown-linux-gnu.tar.gz"
+    );
+    assert_eq!(
+        json["files"][0]["checksum_url"],
+        "https://github.com/axodotdev/axo/releases/download/v0.2.0/axo-x86_64-unknown-linux-gnu.tar.gz.sha256"
+    );
+}
+
+#[test]
+fn it_focuses_on_one_app_of_a_release() {
+    let manifest = r#"{
+        "announcement_tag": "v0.2.0",
+        "releases": [{
+            "app_name": "axo",
+            "app_version": "0.2.0",
+            "artifacts": ["axo-x86_64-unknown-linux-gnu.tar.gz"]
+        }, {
+            "app_name": "axo-helper",
+            "app_version": "0.2.0",
+            "artifacts": ["axo-helper-x86_64-unknown-linux-gnu.tar.gz"]
+        }],
+        "artifacts": {
+            "axo-x86_64-unknown-linux-gnu.tar.gz": {
+                "name": "axo-x86_64-unknown-linux-gnu.tar.gz",
+                "kind": "executable-zip",
+                "target_triples": ["x86_64-unknown-linux-gnu"]
+            },
+            "axo-helper-x86_64-unknown-linux-gnu.tar.gz": {
+                "name": "axo-helper-x86_64-unknown-linux-gnu.tar.gz",
+                "kind": "executable-zip",
+                "target_triples": ["x86_64-unknown-linux-gnu"]
+            }
+        }
+    }"#;
+    let installers_for = |project_name: &str, app_name: Option<&str>| {
+        let mut config = Config::default();
+        config.project.name = project_name.to_owned();
+        config.components.artifacts = Some(ArtifactsConfig {
+            app_name: app_name.map(|name| name.to_owned()),
+            ..Default::default()
+        });
+        let release = release_from_manifest(manifest, &mut config);
+        let json = release_json(&release, &config);
+        installer_fields(&json, "x86_64-unknown-linux-gnu", "download_url")
+            .into_iter()
+            .map(|url| url.to_owned())
+            .collect::<Vec<_>>()
+    };
+    let url =
+        |name: &str| format!("https://github.com/axodotdev/axo/releases/download/v0.2.0/{name}");
+
+    // By default, we show the app named after the project
+    assert_eq!(
+        installers_for("axo", None),
+        vec![url("axo-x86_64-unknown-linux-gnu.tar.gz")]
+    );
+    // Which can be overridden
+    assert_eq!(
+        installers_for("axo", Some("axo-helper")),
+        vec![url("axo-helper-x86_64-unknown-linux-gnu.tar.gz")]
+    );
+    // If no app has the project's name, we show all of them rather than nothing
+    assert_eq!(installers_for("something-else", None).len(), 2);
+}
+
+#[test]
+fn it_reads_manifests_from_other_cargo_dist_versions() {
+    // A manifest from a much newer cargo-dist, with kinds and fields we don't know about
+    let sha256 = "7e1c4f5c3c2fdfbf4d0ce0e2b3c5ab6a6e0df3ab3e1ba5b1d4f2c6e7a8b9c0d1";
+    let manifest = format!(
+        r#"{{
+        "dist_version": "99.0.0",
+        "announcement_tag": "v0.2.0",
+        "releases": [{{
+            "app_name": "axo",
+            "hosting": {{ "github": {{ "artifact_download_url": "https://example.com" }} }},
+            "artifacts": [
+                "axo-x86_64-unknown-linux-gnu.tar.gz",
+                "axo-installer.sh",
+                "source.tar.gz",
+                "axo-weird"
+            ]
+        }}],
+        "artifacts": {{
+            "axo-x86_64-unknown-linux-gnu.tar.gz": {{
+                "name": "axo-x86_64-unknown-linux-gnu.tar.gz",
+                "kind": "executable-zip",
+                "target_triples": ["x86_64-unknown-linux-gnu"],
+                "checksums": {{ "sha256": "{sha256}" }}
+            }},
+            "axo-installer.sh": {{
+                "name": "axo-installer.sh",
+                "kind": "installer",
+                "target_triples": ["x86_64-unknown-linux-gnu"],
+                "install_hint": "curl -LsSf https://example.com/axo-installer.sh | sh"
+            }},
+            "source.tar.gz": {{
+                "name": "source.tar.gz",
+                "kind": "source-tarball"
+            }},
+            "axo-weird": {{
+                "kind": "installer",
+                "target_triples": "x86_64-unknown-linux-gnu"
+            }}
+        }}
+    }}"#
+    );
+    let mut config = Config::default();
+    let release = release_from_manifest(&manifest, &mut config);
+    assert!(release.manifest.is_some());
+
+    // Everything we understand still works, and we skip the rest
+    let json = release_json(&release, &config);
+    assert_eq!(
+        installer_fields(&json, "x86_64-unknown-linux-gnu", "label"),
+        vec!["shell", "tarball"]
+    );
+    let tarball = json["files"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|file| file["name"] == "axo-x86_64-unknown-linux-gnu.tar.gz")
+        .unwrap();
+    assert_eq!(tarball["sha256"], sha256);
+}
+
+#[test]
+fn it_reads_manifests_from_before_cargo_dist_was_stable() {
+    let manifest = r#"{
+        "dist_version": "0.0.2",
+        "releases": [{
+            "app_name": "axo",
+            "app_version": "0.2.0",
+            "artifacts": ["axo-v0.2.0-x86_64-unknown-linux-gnu.tar.gz"]
+        }],
+        "artifacts": {
+            "axo-v0.2.0-x86_64-unknown-linux-gnu.tar.gz": {
+                "name": "axo-v0.2.0-x86_64-unknown-linux-gnu.tar.gz",
+                "kind": "executable-zip",
+                "target_triples": ["x86_64-unknown-linux-gnu"]
+            }
+        }
+    }"#;
+    let mut config = Config::default();
+    let release = release_from_manifest(manifest, &mut config);
+    assert!(release.manifest.is_some());
+
+    let json = release_json(&release, &config);
+    assert_eq!(
+        installer_fields(&json, "x86_64-unknown-linux-gnu", "file"),
+        vec!["axo-v0.2.0-x86_64-unknown-linux-gnu.tar.gz"]
+    );
+}
@@ -0,0 +1,9 @@
+use oranda::config::{ApplyLayer, ArtifactsConfig, ArtifactsLayer};
+
+/// Artifacts config from the JSON you'd write in oranda.json
+pub fn artifacts_config(layer: &str) -> ArtifactsConfig {
+    let layer: ArtifactsLayer = serde_json::from_str(layer).unwrap();
+    let mut config = ArtifactsConfig::default();
+    config.apply_layer(layer);
+    config
+}
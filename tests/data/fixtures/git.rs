@@ -0,0 +1,23 @@
+use std::process::Command;
+
+use camino::Utf8Path;
+
+/// Run git in `dir`, as someone who can make commits and tags
+pub fn git(dir: &Utf8Path, args: &[&str]) {
+    let status = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["-c", "user.name=axo", "-c", "user.email=axo@example.com"])
+        .args(args)
+        .status()
+        .expect("failed to run git");
+    assert!(status.success(), "git {args:?} failed");
+}
+
+/// Make a git repository in `dir` with a single commit to tag
+pub fn init_repo(dir: &Utf8Path) {
+    std::fs::write(dir.join("README.md"), "# axo").expect("failed to write README");
+    git(dir, &["init", "-q"]);
+    git(dir, &["add", "."]);
+    git(dir, &["commit", "-q", "-m", "initial commit"]);
+}
@@ -0,0 +1,15 @@
+use assert_fs::TempDir;
+use camino::{Utf8Path, Utf8PathBuf};
+
+pub mod config;
+pub mod git;
+pub mod release;
+
+/// Make a temporary directory, which is deleted when the `TempDir` is dropped
+pub fn temp_dir() -> (TempDir, Utf8PathBuf) {
+    let tempdir = TempDir::new().expect("failed creating tempdir");
+    let temppath = Utf8Path::from_path(tempdir.path())
+        .expect("non-utf8 temp path")
+        .to_owned();
+    (tempdir, temppath)
+}
@@ -0,0 +1,147 @@
+use assert_fs::fixture::{FileWriteStr, PathChild};
+
+use oranda::config::{ArtifactsConfig, Config};
+use oranda::data::artifacts::File;
+use oranda::data::git::GitTagRelease;
+use oranda::data::github::GithubRepo;
+use oranda::data::{Context, CurrentStateRelease, Release, ReleaseSource};
+use oranda::site::artifacts::json;
+
+use super::temp_dir;
+use crate::utils::tokio_utils::TEST_RUNTIME;
+
+/// A git tag to make a release from
+pub fn tag_source(tag_name: &str, date: &str) -> ReleaseSource {
+    ReleaseSource::GitTag(GitTagRelease {
+        tag_name: tag_name.to_owned(),
+        message: None,
+        date: Some(date.to_owned()),
+        prerelease: false,
+    })
+}
+
+/// Make a release the way we do while building a site
+pub fn release_from(source: ReleaseSource, repo: Option<&GithubRepo>, config: &Config) -> Release {
+    TEST_RUNTIME
+        .block_on(Release::new(source, repo, config))
+        .unwrap()
+}
+
+/// A release of the project as it is right now, rather than of a tag
+pub fn current_release(version: &str, repo: Option<&GithubRepo>, config: &Config) -> Release {
+    let source = ReleaseSource::CurrentState(CurrentStateRelease {
+        version: Some(version.to_owned()),
+        date: None,
+        prerelease: false,
+    });
+    release_from(source, repo, config)
+}
+
+/// A release of v0.2.0 of axo, previewing the artifacts listed in this dist-manifest.json
+///
+/// The config is pointed at the manifest along the way.
+pub fn release_from_manifest(manifest: &str, config: &mut Config) -> Release {
+    let (tempdir, temppath) = temp_dir();
+    tempdir
+        .child("dist-manifest.json")
+        .write_str(manifest)
+        .expect("failed to write manifest");
+
+    let artifacts = config
+        .components
+        .artifacts
+        .get_or_insert_with(ArtifactsConfig::default);
+    artifacts.cargo_dist = true;
+    artifacts.dist_manifest_path = temppath.join("dist-manifest.json").to_string();
+    current_release("0.2.0", Some(&axo_repo()), config)
+}
+
+/// A release of a git tag, without any artifacts
+pub fn tag_release(tag_name: &str, date: &str) -> Release {
+    release_from(tag_source(tag_name, date), None, &Config::default())
+}
+
+/// The repo our pretend releases come from
+pub fn axo_repo() -> GithubRepo {
+    GithubRepo::from_url("https://github.com/axodotdev/axo").unwrap()
+}
+
+/// A file we know nothing about other than where it is
+pub fn file(name: &str, download_url: &str) -> File {
+    File {
+        name: name.to_owned(),
+        download_url: download_url.to_owned(),
+        view_path: None,
+        checksum_file: None,
+        sha256: None,
+        companion_files: vec![],
+        size: None,
+        updated_at: None,
+        download_count: None,
+        infer: true,
+    }
+}
+
+pub const TARBALL_URL: &str = "https://example.com/axo-x86_64-unknown-linux-gnu.tar.gz";
+
+/// Add files hosted at example.com
+pub fn add_files(release: &mut Release, names: &[&str]) {
+    for name in names {
+        release
+            .artifacts
+            .add_file(file(name, &format!("https://example.com/{name}")));
+    }
+}
+
+/// A release with a single tarball for x86_64 linux, with installers already selected
+pub fn release_with_tarball() -> Release {
+    release_with_files(&[])
+}
+
+/// Like [`release_with_tarball`][], but with some extra files next to the tarball
+pub fn release_with_files(extra_files: &[&str]) -> Release {
+    let mut release = tag_release("v0.1.0", "2023-06-01T00:00:00Z");
+    release.artifacts.add_file(File {
+        size: Some(1_234_567),
+        updated_at: Some("2023-06-01T00:00:00Z".to_owned()),
+        download_count: Some(42),
+        ..file("axo-x86_64-unknown-linux-gnu.tar.gz", TARBALL_URL)
+    });
+    add_files(&mut release, extra_files);
+    release.artifacts.add_inference();
+    release
+        .artifacts
+        .select_installers(&ArtifactsConfig::default());
+    release
+}
+
+/// A site context for the given releases, the first of which is the latest
+pub fn context_with(releases: Vec<Release>) -> Context {
+    Context {
+        repo: None,
+        releases,
+        has_prereleases: false,
+        latest_release: Some(0),
+        has_artifacts: true,
+        channels: vec![],
+    }
+}
+
+/// The JSON we publish for a release
+pub fn release_json(release: &Release, config: &Config) -> serde_json::Value {
+    serde_json::to_value(json::build(release, config)).unwrap()
+}
+
+/// One field of each installer for a target, from a release's JSON
+pub fn installer_fields<'a>(
+    json: &'a serde_json::Value,
+    target: &str,
+    field: &str,
+) -> Vec<&'a str> {
+    json["targets"][target]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|installer| installer[field].as_str().unwrap())
+        .collect()
+}
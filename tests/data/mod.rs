@@ -0,0 +1,7 @@
+mod fixtures;
+
+mod artifacts;
+mod cache;
+mod cargo_dist;
+mod releases;
+mod site;
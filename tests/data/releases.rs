@@ -0,0 +1,143 @@
+use oranda::config::{Config, ReleaseChannelConfig, ReleaseSort, ReleasesConfig};
+use oranda::data::git::GitTagRelease;
+use oranda::data::{Context, ReleaseSource};
+use oranda::site::artifacts;
+
+use super::fixtures::git::{git, init_repo};
+use super::fixtures::release::{release_from, tag_release};
+use super::fixtures::temp_dir;
+use crate::utils::tokio_utils::TEST_RUNTIME;
+
+#[test]
+fn it_reads_git_tags() {
+    let (_tempdir, temppath) = temp_dir();
+    init_repo(&temppath);
+    git(&temppath, &["tag", "-a", "v0.1.0", "-m", "First release"]);
+    git(&temppath, &["tag", "v0.2.0-beta.1"]);
+    git(&temppath, &["tag", "-a", "css-v0.0.1", "-m", "Some css"]);
+
+    let mut releases = GitTagRelease::fetch_all(&temppath, "v*").unwrap();
+    releases.sort_by(|a, b| a.tag_name.cmp(&b.tag_name));
+    assert_eq!(releases.len(), 2);
+
+    assert_eq!(releases[0].tag_name, "v0.1.0");
+    assert_eq!(releases[0].message.as_deref(), Some("First release"));
+    assert!(releases[0].date.is_some());
+    assert!(!releases[0].prerelease);
+
+    assert_eq!(releases[1].tag_name, "v0.2.0-beta.1");
+    assert_eq!(releases[1].message, None);
+    assert!(releases[1].prerelease);
+}
+
+#[test]
+fn it_filters_and_sorts_releases_by_semver() {
+    let releases = vec![
+        // A backported patch release, published after the newest version
+        tag_release("v0.9.1", "2023-06-03T00:00:00Z"),
+        tag_release("css-v0.0.7", "2023-06-02T00:00:00Z"),
+        tag_release("v1.0.0", "2023-06-01T00:00:00Z"),
+        tag_release("v0.9.0", "2023-05-01T00:00:00Z"),
+    ];
+    let config = Config {
+        releases: ReleasesConfig {
+            exclude: vec!["css-v*".to_owned()],
+            sort: ReleaseSort::Semver,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let releases = Context::filter_and_sort_releases(releases, &config.releases).unwrap();
+    let tags: Vec<_> = releases.iter().map(|r| r.source.version_tag()).collect();
+    assert_eq!(tags, vec!["v1.0.0", "v0.9.1", "v0.9.0"]);
+
+    let context = Context::with_releases(None, releases, &config).unwrap();
+    assert_eq!(context.latest().unwrap().source.version_tag(), "v1.0.0");
+}
+
+#[test]
+fn it_filters_and_sorts_git_tag_releases() {
+    let (_tempdir, temppath) = temp_dir();
+    init_repo(&temppath);
+    git(&temppath, &["tag", "v0.9.0"]);
+    git(&temppath, &["tag", "v1.0.0"]);
+    git(&temppath, &["tag", "v0.9.1"]);
+    git(&temppath, &["tag", "v1.1.0-nightly"]);
+    let config = Config {
+        releases: ReleasesConfig {
+            git_tag_pattern: "v*".to_owned(),
+            exclude: vec!["*-nightly".to_owned()],
+            sort: ReleaseSort::Semver,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let _guard = TEST_RUNTIME.enter();
+    let releases = Context::fetch_git_tag_releases(&temppath, &config).unwrap();
+    let tags: Vec<_> = releases.iter().map(|r| r.source.version_tag()).collect();
+    assert_eq!(tags, vec!["v1.0.0", "v0.9.1", "v0.9.0"]);
+}
+
+#[test]
+fn it_finds_the_latest_release_of_each_channel() {
+    let prerelease = |tag_name: &str| {
+        let source = ReleaseSource::GitTag(GitTagRelease {
+            tag_name: tag_name.to_owned(),
+            message: None,
+            date: None,
+            prerelease: true,
+        });
+        release_from(source, None, &Config::default())
+    };
+    let releases = vec![
+        prerelease("nightly-2023-07-02"),
+        prerelease("v1.1.0-beta.1"),
+        tag_release("v1.0.0", "2023-06-01T00:00:00Z"),
+        prerelease("nightly-2023-05-01"),
+    ];
+    let channel = |prerelease: Option<bool>, tags: &[&str]| ReleaseChannelConfig {
+        prerelease,
+        tags: tags.iter().map(|tag| tag.to_string()).collect(),
+    };
+    let mut config = Config::default();
+    config.releases.channels = [
+        ("nightly", channel(None, &["nightly-*"])),
+        ("stable", channel(Some(false), &[])),
+        ("beta", channel(Some(true), &["*-beta.*"])),
+        ("lts", channel(None, &["lts-*"])),
+    ]
+    .into_iter()
+    .map(|(name, channel)| (name.to_owned(), channel))
+    .collect();
+
+    let context = Context::with_releases(None, releases, &config).unwrap();
+    let channels: Vec<_> = context
+        .channels
+        .iter()
+        .map(|channel| {
+            let tag = context.releases[channel.latest_release]
+                .source
+                .version_tag();
+            (channel.name.as_str(), tag)
+        })
+        .collect();
+    // Channels without releases are left out
+    assert_eq!(
+        channels,
+        vec![
+            ("nightly", "nightly-2023-07-02"),
+            ("stable", "v1.0.0"),
+            ("beta", "v1.1.0-beta.1"),
+        ]
+    );
+    assert_eq!(context.default_channel().unwrap().name, "stable");
+    assert_eq!(context.latest().unwrap().source.version_tag(), "v1.0.0");
+
+    // Every channel gets an installer, but only stable is shown until you switch
+    let header = artifacts::header(&context, &config).unwrap();
+    assert!(header.contains(r#"<div class="channel" data-channel="stable">"#));
+    assert!(header.contains(r#"<div class="channel hidden" data-channel="nightly">"#));
+    assert!(header.contains("Install nightly-2023-07-02"));
+}
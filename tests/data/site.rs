@@ -0,0 +1,183 @@
+use oranda::config::{ArtifactsConfig, Config};
+use oranda::data::artifacts::inference::triple_to_readable_name;
+use oranda::data::cache::CacheMode;
+use oranda::site::artifacts::{self, json, latest};
+use oranda::site::changelog;
+
+use super::fixtures::config::artifacts_config;
+use super::fixtures::release::{
+    context_with, file, release_json, release_with_files, release_with_tarball, tag_release,
+    tag_source, TARBALL_URL,
+};
+use crate::utils::http_server::{response, TestServer};
+use crate::utils::tokio_utils::TEST_RUNTIME;
+
+#[test]
+fn it_describes_releases_as_json() {
+    let release = release_with_tarball();
+
+    let json = release_json(&release, &Config::default());
+    assert_eq!(json["schema_version"], json::SCHEMA_VERSION);
+    assert_eq!(json["tag"], "v0.1.0");
+    let installer = &json["targets"]["x86_64-unknown-linux-gnu"][0];
+    assert_eq!(installer["method"], "download");
+    assert_eq!(installer["download_url"], TARBALL_URL);
+    assert_eq!(json["files"][0]["download_url"], TARBALL_URL);
+    assert_eq!(json["files"][0]["size"], 1_234_567);
+}
+
+#[test]
+fn it_redirects_latest_downloads() {
+    let release = release_with_tarball();
+
+    let pages = latest::pages(&release, &Config::default()).unwrap();
+    let redirect = pages
+        .iter()
+        .find(|page| page.filename == "download/latest/x86_64-unknown-linux-gnu.html")
+        .expect("no redirect for x86_64-unknown-linux-gnu");
+    assert!(redirect.contents.contains(r#"http-equiv="refresh""#));
+    assert!(redirect.contents.contains(TARBALL_URL));
+    // There's no installer script, so there's nothing to publish as install.sh
+    assert!(!pages.iter().any(|page| page.filename == "install.sh"));
+}
+
+#[test]
+fn it_publishes_the_installer_script_the_widget_prefers() {
+    let server = TestServer::serve(vec![response("200 OK", &[], "echo axo")]);
+    let mut release = tag_release("v0.1.0", "2023-06-01T00:00:00Z");
+    for name in ["axo-helper-installer.sh", "axo-installer.sh"] {
+        let url = format!("{}/{name}", server.url);
+        release.artifacts.add_file(file(name, &url));
+    }
+    release.artifacts.add_inference();
+    let config = artifacts_config(
+        r#"{
+            "files": { "axo-helper-installer.sh": { "label": "helper" } },
+            "installers": { "shell": { "priority": { "linux": 1, "mac": 1 } } }
+        }"#,
+    );
+    release.artifacts.apply_file_config(&config).unwrap();
+    release.artifacts.select_installers(&config);
+    let mut config = Config::default();
+    config.build.cache_mode = CacheMode::Refresh;
+
+    let _guard = TEST_RUNTIME.enter();
+    let pages = latest::pages(&release, &config).unwrap();
+    let script = pages
+        .iter()
+        .find(|page| page.filename == "install.sh")
+        .expect("no install.sh");
+    assert_eq!(script.contents, "echo axo");
+    assert!(server.requests()[0].starts_with("get /axo-installer.sh "));
+}
+
+#[test]
+fn it_skips_installer_scripts_it_cant_fetch() {
+    let server = TestServer::serve(vec![response("404 Not Found", &[], "")]);
+    let mut release = tag_release("v0.1.0", "2023-06-01T00:00:00Z");
+    let url = format!("{}/axo-installer.sh", server.url);
+    release.artifacts.add_file(file("axo-installer.sh", &url));
+    release.artifacts.add_inference();
+    release
+        .artifacts
+        .select_installers(&ArtifactsConfig::default());
+    let mut config = Config::default();
+    config.build.cache_mode = CacheMode::Refresh;
+
+    let _guard = TEST_RUNTIME.enter();
+    let pages = latest::pages(&release, &config).unwrap();
+    assert!(!pages.iter().any(|page| page.filename == "install.sh"));
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[test]
+fn it_shows_downloads_for_older_releases() {
+    let mut newest = release_with_tarball();
+    newest.source = tag_source("v0.2.0", "2023-07-01T00:00:00Z");
+    let context = context_with(vec![newest, release_with_tarball()]);
+    let mut artifacts_config = ArtifactsConfig::default();
+    artifacts_config
+        .package_managers
+        .preferred
+        .insert("npm".to_owned(), "npm install -g axo".to_owned());
+    let mut config = Config::default();
+    config.components.artifacts = Some(artifacts_config);
+
+    // Each release's page has its own downloads
+    let page = changelog::build_single_release(&config, &context.releases[1]).unwrap();
+    assert!(page.contains(r#"id="downloads""#));
+    assert!(page.contains(TARBALL_URL));
+
+    // Without the changelog, older downloads are inlined on the install page
+    let page = artifacts::page(&context, &config).unwrap();
+    assert!(page.contains("Older Versions"));
+    assert!(page.contains("<summary>v0.1.0"));
+    assert!(!page.contains("<summary>v0.2.0"));
+
+    // With it, we link to the release's page instead
+    config.components.changelog = true;
+    let page = artifacts::page(&context, &config).unwrap();
+    assert!(page.contains(r#"href="/changelog/v0.1.0/#downloads""#));
+    assert!(!page.contains("<summary>"));
+}
+
+#[test]
+fn it_builds_pages_for_each_platform() {
+    let release = release_with_files(&["axo-installer.sh", "axo-x86_64-pc-windows-msvc.zip"]);
+    let mut config = Config::default();
+    config.components.artifacts = Some(ArtifactsConfig::default());
+    let context = context_with(vec![release]);
+
+    let pages = artifacts::target_pages(&context, &config).unwrap();
+    let (_, linux) = pages
+        .iter()
+        .find(|(target, _)| target == "x86_64-unknown-linux-gnu")
+        .unwrap();
+    let linux_name = triple_to_readable_name("x86_64-unknown-linux-gnu");
+    assert!(linux.contains(&format!("Install v0.1.0 on {linux_name}")));
+    assert!(linux.contains("https://example.com/axo-installer.sh"));
+    assert!(linux.contains(&format!(r#"href="{TARBALL_URL}""#)));
+    let (_, windows) = pages
+        .iter()
+        .find(|(target, _)| target == "x86_64-pc-windows-msvc")
+        .unwrap();
+    assert!(windows.contains("https://example.com/axo-x86_64-pc-windows-msvc.zip"));
+    assert!(!windows.contains("axo-installer.sh"));
+    // Platforms with nothing to download don't show up in the widget, so they don't get a page
+    assert!(!pages
+        .iter()
+        .any(|(target, _)| target == "aarch64-apple-darwin"));
+
+    // The install widget links to them, even without javascript
+    let header = artifacts::header(&context, &config).unwrap();
+    let noscript = &header[header.find("<noscript>").unwrap()..];
+    assert!(noscript.contains(r#"href="/artifacts/x86_64-unknown-linux-gnu/""#));
+    assert!(noscript.contains(r#"href="/artifacts/x86_64-pc-windows-msvc/""#));
+}
+
+#[test]
+fn it_builds_an_embeddable_install_widget() {
+    let release = release_with_files(&["axo-installer.sh"]);
+    let mut config = Config::default();
+    config.project.name = "axo".to_owned();
+    config.project.homepage = Some("https://axo.example.com/".to_owned());
+    config.components.artifacts = Some(ArtifactsConfig::default());
+    let context = context_with(vec![release]);
+
+    let embed = artifacts::embed(&context, &config).unwrap();
+    assert!(embed.contains(r#"<div class="artifacts" data-tag="v0.1.0">"#));
+    assert!(embed.contains("https://example.com/axo-installer.sh"));
+
+    // The install page explains how to embed it
+    let page = artifacts::page(&context, &config).unwrap();
+    assert!(page.contains(
+        r#"data-copy="<iframe src=&quot;https://axo.example.com/embed/install/&quot; title=&quot;Install axo&quot;"#
+    ));
+
+    // Including when the site lives under a path prefix
+    config.build.path_prefix = Some("axo".to_owned());
+    let page = artifacts::page(&context, &config).unwrap();
+    assert!(page.contains(
+        r#"data-copy="<iframe src=&quot;https://axo.example.com/axo/embed/install/&quot;"#
+    ));
+}
@@ -2,3 +2,4 @@ pub mod utils;
 
 mod build;
 mod config;
+mod data;